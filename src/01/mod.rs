@@ -0,0 +1,335 @@
+use parse::{numbered_lines, ParseError};
+use solution::Solution;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display};
+use std::slice;
+
+// Set `Direction` as a `Copy` type because it is an 8-bit value, making it more expensive to reference.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Direction { West, North, East, South }
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Angle { Left, Right, Straight, Around }
+
+/// Takes an `Angle` as input against the current `Direction` and uses that to determine the new `Direction`.
+fn change_direction(current: Direction, angle: Angle) -> Direction {
+    const CLOCKWISE: [Direction; 4] = [Direction::West, Direction::North, Direction::East, Direction::South];
+    let quarters = match angle { Angle::Straight => 0, Angle::Right => 1, Angle::Around => 2, Angle::Left => 3 };
+    CLOCKWISE[(current as usize + quarters) % 4]
+}
+
+/// Updates the current position based on the given `Direction` and the amount of blocks to move forward.
+fn move_position(position: &mut (isize, isize), direction: Direction, forward: isize) {
+    match direction {
+        Direction::West  => position.0 -= forward,
+        Direction::North => position.1 += forward,
+        Direction::East  => position.0 += forward,
+        Direction::South => position.1 -= forward,
+    }
+}
+
+/// A single step of the instructions: turn left, right, around, or not at all, and then walk a number of blocks.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Instruction { angle: Angle, blocks: isize }
+
+/// Why an instruction could not be parsed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// The instruction does not begin with one of the `L`, `R`, `F`, `B`, or `U` commands.
+    UnknownCommand,
+    /// The command is not followed by the number of blocks to walk.
+    InvalidBlocks,
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::UnknownCommand => "expected the instruction to begin with 'L', 'R', 'F', 'B', or 'U'",
+            ErrorKind::InvalidBlocks  => "expected the number of blocks to walk after the command",
+        })
+    }
+}
+
+/// Parses instructions such as `R2, L3`, which may be separated by commas, whitespace, or newlines. Each
+/// instruction is a command followed by the number of blocks to walk: `L` and `R` turn left or right, `F` walks
+/// forward without turning, and `B` and `U` turn around before walking.
+pub fn parse_instructions(input: &str) -> Result<Vec<Instruction>, ParseError<ErrorKind>> {
+    let mut instructions = Vec::new();
+    for (number, line) in numbered_lines(input) {
+        for text in line.split(|c: char| c == ',' || c.is_whitespace()).filter(|text| !text.is_empty()) {
+            let angle = match text.chars().next() {
+                Some('L') => Angle::Left,
+                Some('R') => Angle::Right,
+                Some('F') => Angle::Straight,
+                Some('B') | Some('U') => Angle::Around,
+                _ => return Err(ParseError::new(ErrorKind::UnknownCommand, number, line, text)),
+            };
+            let blocks = match text[1..].parse::<u32>() {
+                Ok(blocks) => blocks as isize,
+                Err(_) => return Err(ParseError::new(ErrorKind::InvalidBlocks, number, line, &text[1..])),
+            };
+            instructions.push(Instruction { angle: angle, blocks: blocks });
+        }
+    }
+    Ok(instructions)
+}
+
+/// A block that was walked into, where the block that the walk begins at is step zero.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Step {
+    pub index:    usize,
+    pub position: (isize, isize),
+    /// The direction that was faced while walking into the block.
+    pub heading:  Direction,
+}
+
+/// Follows the instructions from the origin while facing north, yielding every block that is walked into.
+#[derive(Clone, Debug)]
+pub struct Walk<'a> {
+    instructions: slice::Iter<'a, Instruction>,
+    position:     (isize, isize),
+    heading:      Direction,
+    remaining:    isize,
+    index:        usize,
+}
+
+impl<'a> Walk<'a> {
+    pub fn new(instructions: &'a [Instruction]) -> Walk<'a> {
+        Walk { instructions: instructions.iter(), position: (0, 0), heading: Direction::North, remaining: 0, index: 0 }
+    }
+
+    /// Lazily yields every step that walks into a block that was already visited, in the order that they are
+    /// walked. Each visited block is kept in a hash set, so every step is checked in constant time.
+    pub fn revisits(self) -> Revisits<'a> {
+        let mut visited = HashSet::new();
+        visited.insert((0, 0));
+        Revisits { walk: self, visited: visited }
+    }
+
+    /// The southwest and northeast corners of the smallest rectangle that contains every visited block.
+    pub fn bounding_box(self) -> ((isize, isize), (isize, isize)) {
+        self.fold(((0, 0), (0, 0)), |(min, max), step| {
+            let (x, y) = step.position;
+            ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+        })
+    }
+
+    /// The first step that reaches the furthest distance from the origin, unless no blocks were walked.
+    pub fn furthest(self) -> Option<Step> {
+        self.fold(None, |furthest: Option<Step>, step| match furthest {
+            Some(furthest) if distance(furthest.position) >= distance(step.position) => Some(furthest),
+            _ => Some(step),
+        })
+    }
+
+    /// Draws the visited blocks as a grid with north at the top, where `S` is the origin, `E` is the final
+    /// block, `X` is a block that was visited more than once, `#` is a block that was visited once, and `.`
+    /// is a block that was never visited.
+    pub fn to_ascii(self) -> String {
+        let ((min_x, min_y), (max_x, max_y)) = self.clone().bounding_box();
+        let mut visits = HashMap::new();
+        visits.insert((0, 0), 1);
+        let mut end = (0, 0);
+        for step in self {
+            *visits.entry(step.position).or_insert(0) += 1;
+            end = step.position;
+        }
+
+        let mut output = String::new();
+        for y in (min_y..max_y + 1).rev() {
+            for x in min_x..max_x + 1 {
+                output.push(match visits.get(&(x, y)) {
+                    _ if (x, y) == (0, 0) => 'S',
+                    _ if (x, y) == end    => 'E',
+                    Some(&1)              => '#',
+                    Some(_)               => 'X',
+                    None                  => '.',
+                });
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Draws the path as an SVG image with north at the top, marking the origin in green, and every block that
+    /// is revisited in red.
+    pub fn to_svg(self) -> String {
+        let ((min_x, min_y), (max_x, max_y)) = self.clone().bounding_box();
+        let mut points = vec![String::from("0,0")];
+        points.extend(self.clone().map(|step| format!("{},{}", step.position.0, -step.position.1)));
+
+        let mut output = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+            min_x - 1, -max_y - 1, max_x - min_x + 2, max_y - min_y + 2);
+        output.push_str(&format!("  <polyline points=\"{}\" fill=\"none\" stroke=\"black\" stroke-width=\"0.2\"/>\n",
+            points.join(" ")));
+        output.push_str("  <circle cx=\"0\" cy=\"0\" r=\"0.4\" fill=\"green\"/>\n");
+        for step in self.revisits() {
+            output.push_str(&format!("  <circle cx=\"{}\" cy=\"{}\" r=\"0.4\" fill=\"red\"/>\n", step.position.0,
+                -step.position.1));
+        }
+        output.push_str("</svg>\n");
+        output
+    }
+}
+
+impl<'a> Iterator for Walk<'a> {
+    type Item = Step;
+    fn next(&mut self) -> Option<Step> {
+        while self.remaining == 0 {
+            let instruction = self.instructions.next()?;
+            self.heading = change_direction(self.heading, instruction.angle);
+            self.remaining = instruction.blocks;
+        }
+        move_position(&mut self.position, self.heading, 1);
+        self.remaining -= 1;
+        self.index += 1;
+        Some(Step { index: self.index, position: self.position, heading: self.heading })
+    }
+}
+
+/// The steps of a walk that revisit a block, which are found by remembering every block that was visited.
+#[derive(Clone, Debug)]
+pub struct Revisits<'a> {
+    walk:    Walk<'a>,
+    visited: HashSet<(isize, isize)>,
+}
+
+impl<'a> Iterator for Revisits<'a> {
+    type Item = Step;
+    fn next(&mut self) -> Option<Step> {
+        let visited = &mut self.visited;
+        self.walk.find(|step| !visited.insert(step.position))
+    }
+}
+
+/// The number of blocks between the origin and the `position`.
+fn distance(position: (isize, isize)) -> isize { position.0.abs() + position.1.abs() }
+
+/// Calculates the distance of the final point and returns it's position for the first part of the puzzle.
+pub fn calculate_distance_for_final_point(instructions: &[Instruction]) -> ((isize, isize), isize) {
+    let mut position = (0isize, 0isize);
+    let mut direction = Direction::North;
+
+    for instruction in instructions {
+        direction = change_direction(direction, instruction.angle);
+        move_position(&mut position, direction, instruction.blocks);
+    }
+
+    (position, position.0.abs() + position.1.abs())
+}
+
+/// Calculates the distance of the first block that is visited twice and returns it's position for the second part.
+/// If no block is visited twice, the final point is used instead.
+pub fn calculate_distance_from_hq(instructions: &[Instruction]) -> ((isize, isize), isize) {
+    let position = match Walk::new(instructions).revisits().next() {
+        Some(revisit) => revisit.position,
+        None => Walk::new(instructions).last().map_or((0, 0), |step| step.position),
+    };
+    (position, distance(position))
+}
+
+/// Measures how many blocks away the final point, and the first point that is visited twice, are.
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Parsed  = Vec<Instruction>;
+    type PartOne = isize;
+    type PartTwo = isize;
+    type Error   = ErrorKind;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>, ParseError<ErrorKind>> { parse_instructions(input) }
+
+    fn part_one(instructions: &Vec<Instruction>) -> isize { calculate_distance_for_final_point(instructions).1 }
+
+    fn part_two(instructions: &Vec<Instruction>) -> isize { calculate_distance_from_hq(instructions).1 }
+}
+
+#[test]
+fn part_one() {
+    let inputs = "R5, L5, R5, R3";
+    assert_eq!(((10,2), 12), calculate_distance_for_final_point(&parse_instructions(inputs).unwrap()));
+    let inputs = "R2, R2, R2";
+    assert_eq!(((0,-2), 2), calculate_distance_for_final_point(&parse_instructions(inputs).unwrap()));
+    let inputs = "R2, L3";
+    assert_eq!(((2,3), 5), calculate_distance_for_final_point(&parse_instructions(inputs).unwrap()));
+}
+
+#[test]
+fn part_two() {
+    let inputs = "R8, R4, R4, R8";
+    assert_eq!(((4,0), 4), calculate_distance_from_hq(&parse_instructions(inputs).unwrap()));
+    let inputs = "R4, L1, R1, L2, L2, L2, R2, L2";
+    assert_eq!(((1,0), 1), calculate_distance_from_hq(&parse_instructions(inputs).unwrap()));
+    let inputs = "R2, R1, L2, L2, L5, L2, L2, R1, L4, L5, L3, L5";
+    assert_eq!(((2,1), 3), calculate_distance_from_hq(&parse_instructions(inputs).unwrap()));
+}
+
+#[test]
+fn parse_errors() {
+    let error = parse_instructions("R2, L3\nR5, X5").unwrap_err();
+    assert_eq!((ErrorKind::UnknownCommand, 2, 5, "X5"), (error.kind, error.line, error.column, error.text.as_str()));
+    let error = parse_instructions("R2, Lx3").unwrap_err();
+    assert_eq!((ErrorKind::InvalidBlocks, 1, 6, "x3"), (error.kind, error.line, error.column, error.text.as_str()));
+    assert_eq!(ErrorKind::InvalidBlocks, parse_instructions("R").unwrap_err().kind);
+    let error = parse_instructions("F1 L2,\n\tT3").unwrap_err();
+    assert_eq!((ErrorKind::UnknownCommand, 2, 2, "T3"), (error.kind, error.line, error.column, error.text.as_str()));
+}
+
+#[test]
+fn commands() {
+    // Forward keeps the heading, while both `B` and `U` turn around.
+    assert_eq!(((0,5), 5), calculate_distance_for_final_point(&parse_instructions("L0 R0 F5").unwrap()));
+    assert_eq!(((2,3), 5), calculate_distance_for_final_point(&parse_instructions("R2,L3").unwrap()));
+    assert_eq!(((1,-1), 2), calculate_distance_for_final_point(&parse_instructions("R3\nU2\nL1").unwrap()));
+    assert_eq!(((0,-2), 2), calculate_distance_for_final_point(&parse_instructions("F2, B4").unwrap()));
+    let instructions = parse_instructions("F2 B1").unwrap();
+    assert_eq!(Some((0, 1)), Walk::new(&instructions).revisits().next().map(|step| step.position));
+}
+
+#[test]
+fn walking() {
+    let instructions = parse_instructions("R2, L1").unwrap();
+    let steps = Walk::new(&instructions).collect::<Vec<Step>>();
+    assert_eq!(vec![
+        Step { index: 1, position: (1, 0), heading: Direction::East },
+        Step { index: 2, position: (2, 0), heading: Direction::East },
+        Step { index: 3, position: (2, 1), heading: Direction::North },
+    ], steps);
+    assert_eq!("..E\nS##\n", Walk::new(&instructions).to_ascii());
+
+    let instructions = parse_instructions("R8, R4, R4, R8").unwrap();
+    let walk = Walk::new(&instructions);
+    let revisits = walk.clone().revisits().collect::<Vec<Step>>();
+    assert_eq!(vec![Step { index: 20, position: (4, 0), heading: Direction::North }], revisits);
+    assert_eq!(((0, -4), (8, 4)), walk.clone().bounding_box());
+    assert_eq!(Some(Step { index: 12, position: (8, -4), heading: Direction::South }), walk.clone().furthest());
+    assert_eq!(None, Walk::new(&[]).furthest());
+
+    let svg = walk.to_svg();
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-1 -5 10 10\">\n"));
+    assert!(svg.contains("<circle cx=\"4\" cy=\"0\" r=\"0.4\" fill=\"red\"/>"));
+}
+
+/// Measures the second part against a synthetic input of 100,000 instructions, which walks a staircase to the
+/// northeast that never crosses itself, until the last two instructions turn back across it, for over a million
+/// blocks in all, which should take well under two seconds. Run it with `cargo test --release -- --ignored synthetic`.
+#[test]
+#[ignore]
+fn synthetic_benchmark() {
+    use bench::{measure, Config};
+
+    let mut input = (0..99_998).map(|index| format!("{}{}", if index % 2 == 0 { 'R' } else { 'L' }, 1 + index % 20))
+        .collect::<Vec<String>>();
+    input.extend_from_slice(&[String::from("L1"), String::from("L20")]);
+    let instructions = parse_instructions(&input.join(", ")).unwrap();
+    assert_eq!(100_000, instructions.len());
+
+    let blocks = Walk::new(&instructions).count();
+    let revisit = Walk::new(&instructions).revisits().next().unwrap();
+    assert!(revisit.index > blocks - 20);
+
+    let summary = measure(Config { warmup: 1, iterations: 10 }, || calculate_distance_from_hq(&instructions));
+    assert!(summary.median < 2_000_000_000, "{:?}", summary);
+}
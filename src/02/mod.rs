@@ -0,0 +1,165 @@
+use parse::{numbered_lines, ParseError};
+use solution::Solution;
+use std::fmt::{self, Display};
+use std::str::Lines;
+
+/// The keypad layout that is described in the first part of the puzzle.
+const PAD: [[char; 3]; 3] = [['1', '2', '3'],
+                             ['4', '5', '6'],
+                             ['7', '8', '9']];
+
+/// Follows each line of instructions around the regular keypad, yielding the digit that each line ends on.
+pub struct FirstDigitSelector<'a> {
+    input: Lines<'a>,
+    x: usize,
+    y: usize
+}
+
+impl<'a> FirstDigitSelector<'a> {
+    pub fn new(input: &'a str) -> FirstDigitSelector<'a> {
+        FirstDigitSelector { input: input.lines(), x: 1, y: 1 }
+    }
+}
+
+impl<'a> Iterator for FirstDigitSelector<'a> {
+    type Item = char;
+    fn next(&mut self) -> Option<char> {
+        self.input.next().map(|line| {
+            for character in line.chars() {
+                match character {
+                    'U' if self.y != 0 => self.y -= 1,
+                    'D' if self.y != 2 => self.y += 1,
+                    'L' if self.x != 0 => self.x -= 1,
+                    'R' if self.x != 2 => self.x += 1,
+                    _   => (),
+                }
+            }
+            PAD[self.y][self.x]
+        })
+    }
+}
+
+/// The diamond-shaped keypad layout from the second part of the puzzle, where `'0'` marks a missing key.
+const PAD_TWO: [[char; 5]; 5] = [['0', '0', '1', '0', '0'],
+                                 ['0', '2', '3', '4', '0'],
+                                 ['5', '6', '7', '8', '9'],
+                                 ['0', 'A', 'B', 'C', '0'],
+                                 ['0', '0', 'D', '0', '0']];
+
+/// Follows each line of instructions around the diamond-shaped keypad, yielding the digit that each line ends on.
+pub struct SecondDigitSelector<'a> {
+   input: Lines<'a>,
+   x: usize,
+   y: usize,
+}
+
+impl<'a> SecondDigitSelector<'a> {
+   pub fn new(input: &'a str) -> SecondDigitSelector<'a> {
+       SecondDigitSelector { input: input.lines(), x: 0, y: 2 }
+   }
+
+   fn can_move_up(&self) -> bool {
+       self.x != 0 && self.x != 4 && self.y != 0 && !(self.x == 1 && self.y == 1) && !(self.x == 3 && self.y == 1)
+   }
+
+   fn can_move_down(&self) -> bool {
+       self.x != 0 && self.x != 4 && self.y != 4 && !(self.x == 1 && self.y == 3) && !(self.x == 3 && self.y == 3)
+   }
+
+   fn can_move_left(&self) -> bool {
+       self.x != 0 && self.y != 0 && self.y != 4 && !(self.x == 1 && self.y == 1) && !(self.x == 1 && self.y == 3)
+   }
+
+   fn can_move_right(&self) -> bool {
+       self.x != 4 && self.y != 0 && self.y != 4 && !(self.x == 3 && self.y == 1) && !(self.x == 3 && self.y == 3)
+   }
+}
+
+impl<'a> Iterator for SecondDigitSelector<'a> {
+   type Item = char;
+   fn next(&mut self) -> Option<char> {
+       self.input.next().map(|line| {
+           for character in line.chars() {
+               match character {
+                   'U' if self.can_move_up()    => self.y -= 1,
+                   'D' if self.can_move_down()  => self.y += 1,
+                   'L' if self.can_move_left()  => self.x -= 1,
+                   'R' if self.can_move_right() => self.x += 1,
+                   _   => (),
+               }
+           }
+           PAD_TWO[self.y][self.x]
+       })
+   }
+}
+
+/// Why the instructions could not be parsed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// A character other than `U`, `D`, `L`, or `R` was found.
+    UnknownMove,
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ErrorKind::UnknownMove => f.write_str("expected a move of 'U', 'D', 'L', or 'R'"),
+        }
+    }
+}
+
+/// Ensures that every line of the instructions consists only of moves.
+pub fn validate(input: &str) -> Result<(), ParseError<ErrorKind>> {
+    for (number, line) in numbered_lines(input) {
+        if let Some((index, character)) = line.char_indices().find(|&(_, c)| !"UDLR".contains(c)) {
+            let text = &line[index..index + character.len_utf8()];
+            return Err(ParseError::new(ErrorKind::UnknownMove, number, line, text));
+        }
+    }
+    Ok(())
+}
+
+/// Collects the bathroom codes for both keypad layouts.
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Parsed  = String;
+    type PartOne = String;
+    type PartTwo = String;
+    type Error   = ErrorKind;
+
+    fn parse(input: &str) -> Result<String, ParseError<ErrorKind>> {
+        validate(input).map(|_| String::from(input))
+    }
+
+    fn part_one(inputs: &String) -> String { FirstDigitSelector::new(inputs).collect() }
+
+    fn part_two(inputs: &String) -> String { SecondDigitSelector::new(inputs).collect() }
+}
+
+#[test]
+fn pad_one_test() {
+    let input = "ULL\nRRDDD\nLURDL\nUUUUD\n";
+    let expected = vec!['1', '9', '8', '5'];
+    for (actual, expected) in FirstDigitSelector::new(input).zip(expected) {
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn pad_two_test() {
+    let input = "ULL\nRRDDD\nLURDL\nUUUUD\n";
+    let expected = vec!['5', 'D', 'B', '3'];
+    for (actual, expected) in SecondDigitSelector::new(input).zip(expected) {
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(Ok(()), validate("ULL\nRRDDD\n"));
+    let error = validate("ULL\nRRxDD\n").unwrap_err();
+    assert_eq!((2, 3, "x"), (error.line, error.column, error.text.as_str()));
+}
@@ -0,0 +1,125 @@
+use parse::{numbered_lines, ParseError};
+use solution::Solution;
+use std::cmp::max;
+use std::fmt::{self, Display};
+use std::slice::{ChunksExact, Iter};
+
+/// Given the size of each side, this determines if the triangle is possible
+pub fn is_possible(x: u16, y: u16, z: u16) -> bool {
+    let sum = x as u32 + y as u32 + z as u32;
+    let max = max(max(x, y), z) as u32;
+    sum - max > max
+}
+
+/// Why a row of triangle sides could not be parsed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// A side is not a whole number that fits within a `u16`.
+    InvalidSide,
+    /// The row has fewer than three sides.
+    MissingSide,
+    /// The row has more than three sides.
+    ExtraSide,
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::InvalidSide => "expected the length of a side, from 0 to 65535",
+            ErrorKind::MissingSide => "expected three sides",
+            ErrorKind::ExtraSide   => "expected no more than three sides",
+        })
+    }
+}
+
+/// Parses each line of the input as the three sides of a triangle.
+pub fn parse_triangles(input: &str) -> Result<Vec<[u16; 3]>, ParseError<ErrorKind>> {
+    let mut triangles = Vec::new();
+    for (number, line) in numbered_lines(input) {
+        let mut sides = line.split_whitespace();
+        let mut triangle = [0u16; 3];
+        for side in &mut triangle {
+            let text = match sides.next() {
+                Some(text) => text,
+                None => return Err(ParseError::new(ErrorKind::MissingSide, number, line, "")),
+            };
+            *side = text.parse::<u16>().map_err(|_| ParseError::new(ErrorKind::InvalidSide, number, line, text))?;
+        }
+        if let Some(text) = sides.next() { return Err(ParseError::new(ErrorKind::ExtraSide, number, line, text)); }
+        triangles.push(triangle);
+    }
+    Ok(triangles)
+}
+
+/// Iterates one row at a time and returns `true` if the row is a possible triangle.
+pub struct TriangleRowIterator<'a> { triangles: Iter<'a, [u16; 3]> }
+
+impl<'a> TriangleRowIterator<'a> {
+    pub fn new(rows: &'a [[u16; 3]]) -> TriangleRowIterator<'a> { TriangleRowIterator { triangles: rows.iter() } }
+}
+
+impl<'a> Iterator for TriangleRowIterator<'a> {
+    type Item = bool;
+    fn next(&mut self) -> Option<bool> {
+        self.triangles.next().map(|&[x, y, z]| is_possible(x, y, z))
+    }
+}
+
+/// Iterates three rows at a time and returns the number of triangles that are were possible in each iteration.
+pub struct TriangleColumnIterator<'a> { rows: ChunksExact<'a, [u16; 3]> }
+
+impl<'a> TriangleColumnIterator<'a> {
+    pub fn new(rows: &'a [[u16; 3]]) -> TriangleColumnIterator<'a> {
+        TriangleColumnIterator { rows: rows.chunks_exact(3) }
+    }
+}
+
+impl<'a> Iterator for TriangleColumnIterator<'a> {
+    type Item = usize;
+    fn next(&mut self) -> Option<usize> {
+        self.rows.next().map(|rows| (0..3).filter(|&column| {
+            is_possible(rows[0][column], rows[1][column], rows[2][column])
+        }).count())
+    }
+}
+
+/// Counts the possible triangles, first by rows and then by columns.
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Parsed  = Vec<[u16; 3]>;
+    type PartOne = usize;
+    type PartTwo = usize;
+    type Error   = ErrorKind;
+
+    fn parse(input: &str) -> Result<Vec<[u16; 3]>, ParseError<ErrorKind>> { parse_triangles(input) }
+
+    fn part_one(rows: &Vec<[u16; 3]>) -> usize { TriangleRowIterator::new(rows).filter(|&x| x).count() }
+
+    fn part_two(rows: &Vec<[u16; 3]>) -> usize { TriangleColumnIterator::new(rows).sum() }
+}
+
+#[test]
+fn part_one() {
+    let input = include_str!("input.txt");
+    let valid_triangles = TriangleRowIterator::new(&parse_triangles(input).unwrap()).filter(|&x| x).count();
+    assert_eq!(982, valid_triangles);
+}
+
+#[test]
+fn part_two() {
+    let input = include_str!("input.txt");
+    let valid_triangles = TriangleColumnIterator::new(&parse_triangles(input).unwrap()).sum::<usize>();
+    assert_eq!(1826, valid_triangles);
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(Ok(vec![[5, 10, 25]]), parse_triangles("  5   10   25\n"));
+    let error = parse_triangles("  5   10   25\n  3  4x  5").unwrap_err();
+    assert_eq!((ErrorKind::InvalidSide, 2, 6, "4x"), (error.kind, error.line, error.column, error.text.as_str()));
+    assert_eq!(ErrorKind::MissingSide, parse_triangles("3 4").unwrap_err().kind);
+    assert_eq!(ErrorKind::ExtraSide, parse_triangles("3 4 5 6").unwrap_err().kind);
+}
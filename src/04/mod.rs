@@ -0,0 +1,201 @@
+use parse::{numbered_lines, ParseError};
+use solution::Solution;
+use std::cmp::Ordering::{Less, Greater};
+use std::convert::From;
+use std::fmt::{self, Display};
+use std::slice::Iter;
+
+// Used to eliminate dynamic heap allocations by allocating a fixed-sized vector on the stack.
+use arrayvec::ArrayVec;
+
+/// Contains the character as a `key` and it's frequency as the `value`
+struct Frequency { key: u8, value: u8 }
+
+/// A map of character frequencies
+pub struct FrequencyMap { data: ArrayVec<Frequency, 26> }
+
+impl FrequencyMap {
+    /// Increment a given character in the map.
+    /// The character's index is guaranteed to be the character's integer representation minus 97.
+    pub fn increment_key(&mut self, key: u8) { self.data[(key - 97) as usize].value += 1; }
+
+    /// Sort the frequency map by the greater number of occurrences first, and alphabetical order second.
+    pub fn sort(&mut self) {
+        self.data.sort_by(|a, b| {
+            if a.value > b.value { Less } else if a.value < b.value || a.key > b.key { Greater } else { Less }
+        })
+    }
+
+    /// Collect the first five characters in the sorted frequency map as the checksum of the map.
+    pub fn collect_checksum(&mut self) -> ArrayVec<u8, 5> {
+        self.sort();
+        self.data.iter().take(5).map(|x| x.key).collect::<ArrayVec<u8, 5>>()
+    }
+}
+
+impl<'a> From<&'a str> for FrequencyMap {
+    fn from(name: &'a str) -> FrequencyMap {
+        let mut freqmap = FrequencyMap {
+            data: (b'a'..b'z' + 1).map(|c| Frequency { key: c, value: 0 }).collect::<ArrayVec<_, 26>>()
+        };
+        for character in name.bytes().filter(|&x| x != b'-') { freqmap.increment_key(character); }
+        freqmap
+    }
+}
+
+/// Take a character as a byte and wrap add the character by the alphabet. 'a' ... 'z' -> 'a' ... 'z' -> ...
+fn wrap_to_char(character: u8, by: u32) -> char {
+    ((character - b'a' + (by % 26) as u8) % 26 + b'a') as char
+}
+
+/// An encrypted room from the list, which may or may not be a real room.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Room {
+    /// The encrypted name, including the dash that separates it from the sector ID.
+    pub name:      String,
+    pub sector_id: u32,
+    pub checksum:  String,
+}
+
+impl Room {
+    /// Determines if the checksum consists of the five most common letters of the name.
+    pub fn is_real(&self) -> bool {
+        &FrequencyMap::from(self.name.as_str()).collect_checksum()[..] == self.checksum.as_bytes()
+    }
+}
+
+/// Why a room could not be parsed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// The name contains a character other than a lowercase letter or a dash.
+    InvalidName,
+    /// The name is not followed by a sector ID.
+    InvalidSectorId,
+    /// The sector ID is not followed by a checksum in square brackets.
+    MissingChecksum,
+    /// The checksum is not five lowercase letters.
+    InvalidChecksum,
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::InvalidName     => "expected the name to consist of lowercase letters and dashes",
+            ErrorKind::InvalidSectorId => "expected a sector ID after the name",
+            ErrorKind::MissingChecksum => "expected a checksum in square brackets after the sector ID",
+            ErrorKind::InvalidChecksum => "expected the checksum to be five lowercase letters",
+        })
+    }
+}
+
+/// Parses each line of the input as a room, such as `aaaaa-bbb-z-y-x-123[abxyz]`.
+pub fn parse_rooms(input: &str) -> Result<Vec<Room>, ParseError<ErrorKind>> {
+    let mut rooms = Vec::new();
+    for (number, line) in numbered_lines(input) {
+        let error = |kind, text| ParseError::new(kind, number, line, text);
+
+        let (prefix, checksum) = match line.find('[') {
+            Some(open) => line.split_at(open),
+            None => return Err(error(ErrorKind::MissingChecksum, &line[line.len()..])),
+        };
+        if !checksum.ends_with(']') { return Err(error(ErrorKind::MissingChecksum, checksum)); }
+        let checksum = &checksum[1..checksum.len() - 1];
+        if checksum.len() != 5 || !checksum.bytes().all(|x| x.is_ascii_lowercase()) {
+            return Err(error(ErrorKind::InvalidChecksum, checksum));
+        }
+
+        let (name, sector_id) = prefix.split_at(prefix.find(|x: char| x.is_ascii_digit()).unwrap_or(prefix.len()));
+        let sector_id = sector_id.parse::<u32>().map_err(|_| error(ErrorKind::InvalidSectorId, sector_id))?;
+        if let Some((index, character)) = name.char_indices().find(|&(_, x)| !x.is_ascii_lowercase() && x != '-') {
+            return Err(error(ErrorKind::InvalidName, &name[index..index + character.len_utf8()]));
+        }
+
+        rooms.push(Room { name: String::from(name), sector_id: sector_id, checksum: String::from(checksum) });
+    }
+    Ok(rooms)
+}
+
+/// Iterates through a list of encrypted rooms and returns the decrypted names and associated room numbers
+/// of the real rooms.
+pub struct RoomIterator<'a> {
+    rooms: Iter<'a, Room>
+}
+
+impl<'a> RoomIterator<'a> {
+    pub fn new(rooms: &'a [Room]) -> RoomIterator<'a> {
+        RoomIterator { rooms: rooms.iter() }
+    }
+}
+
+impl<'a> Iterator for RoomIterator<'a> {
+    type Item = (ArrayVec<char, 5>, u32);
+    fn next(&mut self) -> Option<(ArrayVec<char, 5>, u32)> {
+        self.rooms.by_ref().find(|room| room.is_real()).map(|room| {
+            (room.name.bytes().map(|x| {
+                if x == b'-' { ' ' } else { wrap_to_char(x, room.sector_id) }
+            }).take(5).collect::<ArrayVec<_, 5>>(), room.sector_id)
+        })
+    }
+}
+
+/// Determines if the first five characters of a decrypted room name spell out `north`.
+pub fn room_is_match(room: &[char]) -> bool {
+    room == ['n', 'o', 'r', 't', 'h']
+}
+
+/// Sums the sector IDs of the real rooms, and finds the room where the north pole objects are stored.
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Parsed  = Vec<Room>;
+    type PartOne = u32;
+    type PartTwo = u32;
+    type Error   = ErrorKind;
+
+    fn parse(input: &str) -> Result<Vec<Room>, ParseError<ErrorKind>> { parse_rooms(input) }
+
+    fn part_one(rooms: &Vec<Room>) -> u32 { RoomIterator::new(rooms).fold(0, |acc, room| acc + room.1) }
+
+    fn part_two(rooms: &Vec<Room>) -> u32 {
+        RoomIterator::new(rooms).find(|room| room_is_match(&room.0)).map_or(0, |room| room.1)
+    }
+}
+
+#[test]
+fn part_one() {
+    let inputs = "aaaaa-bbb-z-y-x-123[abxyz]\na-b-c-d-e-f-g-h-987[abcde]\nnot-a-real-room-404[oarel]\n\
+        totally-real-room-200[decoy]\n";
+    let rooms = parse_rooms(inputs).unwrap();
+    assert_eq!(1514, RoomIterator::new(&rooms).fold(0, |acc, x| acc + x.1));
+
+    let rooms = parse_rooms(include_str!("input.txt")).unwrap();
+    assert_eq!(245102, RoomIterator::new(&rooms).fold(0, |acc, x| acc + x.1));
+}
+
+#[test]
+fn part_two() {
+    let rooms = parse_rooms(include_str!("input.txt")).unwrap();
+    let expected = include_str!("decrypted.txt");
+
+    for (room, expected) in RoomIterator::new(&rooms).zip(expected.lines()) {
+        assert!(expected.starts_with(&room.0.iter().cloned().collect::<String>()));
+        assert!(expected.ends_with(&format!("[{}]", room.1)));
+    }
+
+    let room = RoomIterator::new(&rooms).find(|x| room_is_match(&x.0)).unwrap();
+    assert_eq!(324, room.1);
+}
+
+#[test]
+fn parse_errors() {
+    let error = parse_rooms("aaaaa-bbb-z-y-x-123[abxyz]\nnot-a-real-room-404").unwrap_err();
+    assert_eq!((ErrorKind::MissingChecksum, 2, 20), (error.kind, error.line, error.column));
+    let error = parse_rooms("not-a-real-room-4o4[oarel]").unwrap_err();
+    assert_eq!((ErrorKind::InvalidSectorId, 17, "4o4"), (error.kind, error.column, error.text.as_str()));
+    let error = parse_rooms("not-a-Real-room-404[oarel]").unwrap_err();
+    assert_eq!((ErrorKind::InvalidName, 7, "R"), (error.kind, error.column, error.text.as_str()));
+    assert_eq!(ErrorKind::InvalidChecksum, parse_rooms("not-a-real-room-404[oa]").unwrap_err().kind);
+    assert_eq!(ErrorKind::MissingChecksum, parse_rooms("not-a-real-room-404[oarel").unwrap_err().kind);
+}
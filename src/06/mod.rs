@@ -0,0 +1,142 @@
+use arrayvec::ArrayVec;
+use parse::{numbered_lines, ParseError};
+use solution::Solution;
+use std::fmt::{self, Display};
+
+/// Contains the character as a `key` and it's frequency as the `value`
+struct Frequency { key: char, value: u8 }
+
+/// A map of character frequencies
+pub struct FrequencyMap { data: ArrayVec<Frequency, 26> }
+
+impl FrequencyMap {
+    /// Creates a map where every lowercase character has a frequency of zero.
+    pub fn new() -> FrequencyMap {
+        FrequencyMap {
+            data: (b'a'..b'z' + 1).map(|c| Frequency { key: c as char, value: 0 }).collect::<ArrayVec<_, 26>>()
+        }
+    }
+
+    /// Increment a given character in the map.
+    /// The character's index is guaranteed to be the character's integer representation minus 97.
+    pub fn increment_key(&mut self, key: char) { self.data[(key as u8 - 97) as usize].value += 1; }
+
+    /// Collect the most frequent character in the map.
+    pub fn most_frequent(&self) -> char {
+        self.data.iter().fold(('a', 0), |acc, x| if x.value > acc.1 { (x.key, x.value) } else { acc }).0
+    }
+
+    /// Collect the least frequent character in the map.
+    pub fn least_frequent(&self) -> char {
+        self.data.iter().fold(('a', 255), |acc, x| if x.value < acc.1 { (x.key, x.value) } else { acc }).0
+    }
+
+    // Reset the values on the map
+    pub fn reset(&mut self) { for element in &mut self.data { element.value = 0; }}
+}
+
+impl Default for FrequencyMap {
+    fn default() -> FrequencyMap { FrequencyMap::new() }
+}
+
+/// Collects the most and least frequent character of each column as the unmodified and modified messages.
+pub fn get_message(unmodified: &mut [char; 8], modified: &mut [char; 8], inputs: &str) {
+    let mut frequency = FrequencyMap::new();
+
+    for index in 0..8 {
+        for message in inputs.lines() {
+            if let Some(character) = message.chars().nth(index) { frequency.increment_key(character); }
+        }
+        unmodified[index] = frequency.most_frequent();
+        modified[index]   = frequency.least_frequent();
+        frequency.reset();
+    }
+}
+
+/// Collects both messages as strings.
+fn messages(inputs: &str) -> (String, String) {
+    let mut unmodified_message = ['\0'; 8];
+    let mut modified_message   = ['\0'; 8];
+    get_message(&mut unmodified_message, &mut modified_message, inputs);
+    (unmodified_message.iter().cloned().collect(), modified_message.iter().cloned().collect())
+}
+
+/// Why the repeated messages could not be parsed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// A message contains a character other than a lowercase letter.
+    InvalidCharacter,
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ErrorKind::InvalidCharacter => f.write_str("expected the message to consist of lowercase letters"),
+        }
+    }
+}
+
+/// Ensures that every message consists only of lowercase letters.
+pub fn validate(input: &str) -> Result<(), ParseError<ErrorKind>> {
+    for (number, line) in numbered_lines(input) {
+        if let Some((index, character)) = line.char_indices().find(|&(_, x)| !x.is_ascii_lowercase()) {
+            let text = &line[index..index + character.len_utf8()];
+            return Err(ParseError::new(ErrorKind::InvalidCharacter, number, line, text));
+        }
+    }
+    Ok(())
+}
+
+/// Error-corrects the repeated message using the most and least common characters of each column.
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Parsed  = String;
+    type PartOne = String;
+    type PartTwo = String;
+    type Error   = ErrorKind;
+
+    fn parse(input: &str) -> Result<String, ParseError<ErrorKind>> {
+        validate(input).map(|_| String::from(input))
+    }
+
+    fn part_one(inputs: &String) -> String { messages(inputs).0 }
+
+    fn part_two(inputs: &String) -> String { messages(inputs).1 }
+}
+
+#[test]
+fn part_one() {
+    let inputs = r#"eedadn
+drvtee
+eandsr
+raavrd
+atevrs
+tsrnev
+sdttsa
+rasrtv
+nssdts
+ntnada
+svetve
+tesnvt
+vntsnd
+vrdear
+dvrsen
+enarar"#;
+
+    let mut unmodified_message = ['\0'; 8];
+    let mut modified_message = ['\0'; 8];
+    get_message(&mut unmodified_message, &mut modified_message, inputs);
+    for (actual, expected) in unmodified_message.iter().zip(['e', 'a', 's', 't', 'e', 'r'].iter()) {
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(Ok(()), validate("eedadn\ndrvtee\n"));
+    let error = validate("eedadn\ndrvTee\n").unwrap_err();
+    assert_eq!((2, 4, "T"), (error.line, error.column, error.text.as_str()));
+}
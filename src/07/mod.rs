@@ -0,0 +1,298 @@
+use arrayvec::ArrayVec;
+use parse::{numbered_lines, ParseError};
+use solution::Solution;
+use std::fmt::{self, Display};
+
+/// Designates a string slice as either being an `Inner` or `Outer` token.
+pub enum IPToken<'a> {
+    Inner(&'a str),
+    Outer(&'a str),
+}
+
+/// Given an `IPv7` address as input, it will separate the `Inner` tokens from the `Outer` tokens.
+pub struct IPTokenizer<'a> {
+    line:    &'a str,
+    read:    usize,
+    bracket: bool
+}
+
+impl<'a> IPTokenizer<'a> {
+    pub fn new(input: &'a str) -> IPTokenizer<'a> {
+        IPTokenizer { line: input, read: 0, bracket: false }
+    }
+}
+
+impl<'a> Iterator for IPTokenizer<'a> {
+    type Item = IPToken<'a>;
+    fn next(&mut self) -> Option<IPToken<'a>> {
+        if self.bracket {
+            for (mut id, character) in self.line.chars().skip(self.read).enumerate() {
+                if character == ']' {
+                    id += self.read;
+                    let token = &self.line[self.read..id];
+                    self.read = id+1;
+                    self.bracket = false;
+                    return Some(IPToken::Inner(token));
+                }
+            }
+        } else {
+            for (mut id, character) in self.line.chars().skip(self.read).enumerate() {
+                if character == '[' {
+                    id += self.read;
+                    let token = &self.line[self.read..id];
+                    self.read = id+1;
+                    self.bracket = true;
+                    return Some(IPToken::Outer(token));
+                }
+            }
+        }
+        if self.read == self.line.len() {
+            None
+        } else {
+            let token = &self.line[self.read..];
+            self.read = self.line.len();
+            Some(IPToken::Outer(token))
+        }
+    }
+}
+
+/// Check if a string slice contains an **ABBA** pattern
+pub fn contains_abba(input: &str) -> bool {
+    let mut pattern = ['\0'; 4];
+    let mut chars = input.chars();
+
+    if let (Some(a), Some(b), Some(c), Some(d)) = (chars.next(), chars.next(), chars.next(), chars.next()) {
+        pattern[0] = a;
+        pattern[1] = b;
+        pattern[2] = c;
+        pattern[3] = d;
+        if pattern[0] == pattern[3] && pattern[1] == pattern[2] && pattern[0] != pattern[1] {
+            return true
+        }
+
+        for character in chars {
+            pattern[0] = pattern[1];
+            pattern[1] = pattern[2];
+            pattern[2] = pattern[3];
+            pattern[3] = character;
+            if pattern[0] == pattern[3] && pattern[1] == pattern[2] && pattern[0] != pattern[1] {
+                return true
+            }
+        }
+    }
+    false
+}
+
+/// Meant to be used with `Outer` tokens, it will return all possible **ABA** values for the given token.
+pub struct ABATokenizer<'a> {
+    data:    &'a str,
+    pattern: ArrayVec<char, 3>,
+    read:    usize
+}
+
+impl<'a> ABATokenizer<'a> {
+    pub fn new(input: &'a str) -> ABATokenizer<'a> {
+        ABATokenizer { data: input, read: 0, pattern: ArrayVec::<char, 3>::new() }
+    }
+}
+
+impl<'a> Iterator for ABATokenizer<'a> {
+    type Item = ArrayVec<char, 3>;
+    fn next(&mut self) -> Option<ArrayVec<char, 3>> {
+        let mut char_iter = self.data.chars().skip(self.read);
+        if self.read == 0 {
+            if let (Some(a), Some(b), Some(c)) = (char_iter.next(), char_iter.next(), char_iter.next()) {
+                self.pattern.push(a);
+                self.pattern.push(b);
+                self.pattern.push(c);
+                self.read += 3;
+                if self.pattern[0] == self.pattern[2] && self.pattern[0] != self.pattern[1] {
+                    return Some(self.pattern.clone())
+                }
+            }
+        }
+
+        for character in char_iter {
+            self.read += 1;
+            self.pattern[0] = self.pattern[1];
+            self.pattern[1] = self.pattern[2];
+            self.pattern[2] = character;
+            if self.pattern[0] == self.pattern[2] && self.pattern[0] != self.pattern[1] { return Some(self.pattern.clone()) }
+        }
+
+        None
+    }
+}
+
+/// Meant to be used with `Inner` tokens, this will check to see if an `Inner` token contains
+/// the **BAB** version of an `Outer` token's **ABA**.
+pub fn contains_bab(input: &str, aba: &ArrayVec<char, 3>) -> bool {
+    let mut pattern = ['\0'; 3];
+    let mut char_iter = input.chars();
+    if let (Some(a), Some(b), Some(c)) = (char_iter.next(), char_iter.next(), char_iter.next()) {
+        pattern[0] = a;
+        pattern[1] = b;
+        pattern[2] = c;
+        if pattern[0] == pattern[2] && pattern[0] != pattern[1] && pattern[0] == aba[1] && pattern[1] == aba[0] {
+            return true
+        } else {
+            for character in char_iter {
+                pattern[0] = pattern[1];
+                pattern[1] = pattern[2];
+                pattern[2] = character;
+                if pattern[0] == pattern[2] && pattern[0] != pattern[1] && pattern[0] == aba[1] && pattern[1] == aba[0] {
+                    return true
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Checks an `IPv7` address's `Inner` and `Outer` tokens to determine if it supports TLS.
+/// TLS support means that the `Inner` tokens do not contain any **ABBA** patterns,
+/// but that the `Outer` tokens do contain at least one **ABBA** pattern.
+pub fn supports_tls(inner: &[&str], outer: &[&str]) -> bool {
+    if !inner.iter().any(|x| contains_abba(x)) {
+        outer.iter().any(|x| contains_abba(x))
+    } else {
+        false
+    }
+}
+
+/// Checks an `IPv7` address's `Inner` and `Outer` tokens to determine if it supports SSL.
+/// SSL support means that there is at least one **BAB** match in an `Inner` token
+/// that is a reverse of an `Outer` token's **ABA** match.
+pub fn supports_ssl(inner: &[&str], outer: &[&str]) -> bool {
+    for aba in outer.iter().flat_map(|x| ABATokenizer::new(x)) {
+        if inner.iter().any(|token| contains_bab(token, &aba) ) { return true }
+    }
+    false
+}
+
+/// Why an `IPv7` address could not be parsed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// The address contains a character other than a lowercase letter or a square bracket.
+    InvalidCharacter,
+    /// A square bracket is nested, unopened, or left unclosed.
+    UnbalancedBracket,
+    /// The address has more than three sequences within brackets.
+    TooManySequences,
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::InvalidCharacter  => "expected a lowercase letter or a square bracket",
+            ErrorKind::UnbalancedBracket => "expected each '[' to be closed by a ']' before the next '['",
+            ErrorKind::TooManySequences  => "expected no more than three sequences within brackets",
+        })
+    }
+}
+
+/// Ensures that every address can be tokenized, and that its sequences fit within the limits of `calculate`.
+pub fn validate(input: &str) -> Result<(), ParseError<ErrorKind>> {
+    for (number, line) in numbered_lines(input) {
+        let error = |kind, text| Err(ParseError::new(kind, number, line, text));
+        let (mut bracket, mut inner) = (false, 0);
+        for (index, character) in line.char_indices() {
+            let text = &line[index..index + character.len_utf8()];
+            match character {
+                'a'..='z' => (),
+                '[' if !bracket => { bracket = true; inner += 1; },
+                ']' if bracket  => bracket = false,
+                '[' | ']'       => return error(ErrorKind::UnbalancedBracket, text),
+                _               => return error(ErrorKind::InvalidCharacter, text),
+            }
+            if inner > 3 { return error(ErrorKind::TooManySequences, text); }
+        }
+
+        if bracket { return error(ErrorKind::UnbalancedBracket, &line[line.len()..]); }
+    }
+    Ok(())
+}
+
+/// Counts the number of `IPv7` addresses that support TLS and SSL, respectively.
+pub fn calculate(inputs: &str) -> (usize, usize) {
+    let (mut tls_supported, mut ssl_supported) = (0, 0);
+    for line in inputs.lines() {
+        let mut outer: ArrayVec<&str, 5> = ArrayVec::new();
+        let mut inner: ArrayVec<&str, 3> = ArrayVec::new();
+        for token in IPTokenizer::new(line) {
+            match token {
+                IPToken::Inner(content) => { inner.push(content); },
+                IPToken::Outer(content) => { outer.push(content); },
+            }
+        }
+        if supports_tls(&inner, &outer) { tls_supported += 1; }
+        if supports_ssl(&inner, &outer) { ssl_supported += 1; }
+    }
+    (tls_supported, ssl_supported)
+}
+
+/// Counts the `IPv7` addresses that support TLS and SSL.
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Parsed  = String;
+    type PartOne = usize;
+    type PartTwo = usize;
+    type Error   = ErrorKind;
+
+    fn parse(input: &str) -> Result<String, ParseError<ErrorKind>> {
+        validate(input).map(|_| String::from(input))
+    }
+
+    fn part_one(inputs: &String) -> usize { calculate(inputs).0 }
+
+    fn part_two(inputs: &String) -> usize { calculate(inputs).1 }
+}
+
+#[test]
+fn part_one() {
+    let inputs = ["abba[mnop]qrst", "abcd[bddb]xyyx", "aaaa[qwer]tyui", "ioxxoj[asdfgh]zxcvbn"];
+    let expected = [true, false, false, true];
+    for (count, line) in inputs.iter().enumerate() {
+        let mut outer: ArrayVec<&str, 5> = ArrayVec::new();
+        let mut inner: ArrayVec<&str, 3> = ArrayVec::new();
+        for token in IPTokenizer::new(line) {
+            match token {
+                IPToken::Inner(content) => { inner.push(content); },
+                IPToken::Outer(content) => { outer.push(content); },
+            }
+        }
+        println!("#{}", count);
+        assert_eq!(expected[count], supports_tls(&inner, &outer));
+    }
+}
+
+#[test]
+fn part_two() {
+    let inputs = ["aba[bab]xyz", "xyx[xyx]xyx", "aaa[kek]eke", "zazbz[bzb]cdb, zazbz[acdc]dfas[fsf]adcd"];
+    let expected = [true, false, true, true, true];
+    for (count, line) in inputs.iter().enumerate() {
+        let mut outer: ArrayVec<&str, 5> = ArrayVec::new();
+        let mut inner: ArrayVec<&str, 3> = ArrayVec::new();
+        for token in IPTokenizer::new(line) {
+            match token {
+                IPToken::Inner(content) => { inner.push(content); },
+                IPToken::Outer(content) => { outer.push(content); },
+            }
+        }
+        assert_eq!(expected[count], supports_ssl(&inner, &outer));
+    }
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(Ok(()), validate("abba[mnop]qrst\n[bddb]xyyx[a]b[c]d"));
+    let error = validate("abba[mnop]qrst\nabcd[bd[db]xyyx").unwrap_err();
+    assert_eq!((ErrorKind::UnbalancedBracket, 2, 8), (error.kind, error.line, error.column));
+    assert_eq!(ErrorKind::UnbalancedBracket, validate("abcd[bddb").unwrap_err().kind);
+    assert_eq!(ErrorKind::UnbalancedBracket, validate("abcd]bddb").unwrap_err().kind);
+    assert_eq!(ErrorKind::InvalidCharacter, validate("abcd[bd1b]").unwrap_err().kind);
+    assert_eq!(ErrorKind::TooManySequences, validate("a[b]c[d]e[f]g[h]i").unwrap_err().kind);
+}
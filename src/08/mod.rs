@@ -0,0 +1,416 @@
+use parse::{numbered_lines, ParseError};
+use solution::Solution;
+use std::fmt::{self, Display};
+
+const MASK_6:           u8 = 0b111111;
+const MASK_50:         u64 = 0b11111111111111111111111111111111111111111111111111;
+
+
+fn mersenne_generator(num: u64) -> u64 {
+    (0..num).fold(0, |acc, _| acc + acc + 1)
+}
+
+/// A 50x6 screen of pixels that can be drawn on with rectangles and rotations.
+pub trait Screen {
+    fn enabled_pixels(&self) -> u32;
+    fn rect(&mut self, wide: u8, tall: u8);
+    fn rotate_row(&mut self, row: u8, shift: u8);
+    fn rotate_column(&mut self, column: u8, shift: u8);
+    fn is_lit(&self, row: usize, column: usize) -> bool;
+
+    /// Draws the screen as six lines of text, where `#` is a lit pixel.
+    fn render(&self) -> String {
+        let mut output = String::with_capacity(51 * 6);
+        for row in 0..6 {
+            for column in 0..50 {
+                output.push(if self.is_lit(row, column) { '#' } else { ' ' });
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    fn display_pixels(&self) { print!("{}", self.render()); }
+}
+
+/// A `Screen` that stores each column of pixels as the bits of a byte.
+pub struct Screen8 {
+    data: [u8; 50]
+}
+
+impl Default for Screen8 {
+    fn default() -> Screen8 { Screen8 { data: [0; 50] } }
+}
+
+impl Screen for Screen8 {
+    fn enabled_pixels(&self) -> u32 {
+        self.data.iter().fold(0, |acc, x| acc + x.count_ones())
+    }
+
+    fn rect(&mut self, wide: u8, tall: u8) {
+        let bytes_enable = mersenne_generator(tall as u64) as u8;
+        for row in 0..wide { self.data[row as usize] |= bytes_enable; }
+    }
+
+    fn rotate_column(&mut self, column: u8, shift: u8) {
+        let mask_bits = mersenne_generator((6 - shift) as u64) as u8;
+        let mut current_value = self.data[column as usize];
+        let high = current_value & (u8::MAX ^ mask_bits);
+        self.data[column as usize] = if high == 0 {
+            current_value << shift
+        } else {
+            current_value <<= shift;
+            current_value |= high >> (6 - shift);
+            current_value & MASK_6
+        };
+    }
+
+    fn rotate_row(&mut self, row: u8, shift: u8) {
+        let row_mask     = 1 << row;
+        let mut new_data = [0; 50];
+        for (id, row) in self.data.iter().enumerate() {
+            new_data[id] = row & (u8::MAX ^ row_mask);
+        }
+
+        for (mut id, row) in self.data.iter().enumerate() {
+            id += shift as usize;
+            new_data[if id > 49 { id - 50 } else { id }] += row & row_mask;
+        }
+
+        for (id, row) in self.data.iter_mut().enumerate() {
+            *row = new_data[id];
+        }
+    }
+
+    fn is_lit(&self, row: usize, column: usize) -> bool { self.data[column] & (1 << row) != 0 }
+}
+
+/// A `Screen` that stores each row of pixels as the bits of a `u64`.
+#[derive(Default)]
+pub struct Screen64 {
+    data: [u64; 6]
+}
+
+impl Screen for Screen64 {
+    fn enabled_pixels(&self) -> u32 {
+        self.data.iter().fold(0, |acc, x| acc + x.count_ones())
+    }
+
+    fn rect(&mut self, wide: u8, tall: u8) {
+        let bytes_enable = mersenne_generator(wide as u64);
+        for column in 0..tall { self.data[column as usize] |= bytes_enable; }
+    }
+
+    fn rotate_row(&mut self, row: u8, shift: u8) {
+        let mask_bits = mersenne_generator((50 - shift) as u64);
+        let mut current_value = self.data[row as usize];
+        let high = current_value & (u64::MAX ^ mask_bits);
+        self.data[row as usize] = if high == 0 {
+            current_value << shift
+        } else {
+            current_value <<= shift;
+            current_value |= high >> (50 - shift);
+            current_value & MASK_50
+        };
+    }
+
+    fn rotate_column(&mut self, column: u8, shift: u8) {
+        let column_mask  = 1 << column;
+        let mut new_data = [0; 6];
+
+        for (id, column) in self.data.iter().enumerate() {
+            new_data[id] = column & (u64::MAX ^ column_mask);
+        }
+
+        for (id, column) in self.data.iter().enumerate() {
+            new_data[id] = column & (u64::MAX ^ column_mask);
+        }
+
+        for (mut id, column) in self.data.iter().enumerate() {
+            id += shift as usize;
+            new_data[if id > 5 { id - 6 } else { id }] += column & column_mask;
+        }
+
+        for (id, column) in self.data.iter_mut().enumerate() {
+            *column = new_data[id];
+        }
+    }
+
+    fn is_lit(&self, row: usize, column: usize) -> bool { self.data[row] & (1 << column) != 0 }
+}
+
+/// An instruction for drawing on the screen.
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    Rect(u8, u8),
+    RotateRow(u8, u8),
+    RotateColumn(u8, u8),
+}
+
+/// Why an action could not be parsed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// The line does not begin with `rect` or `rotate`.
+    UnknownAction,
+    /// The `rect` action is not followed by dimensions, such as `3x2`.
+    InvalidRect,
+    /// The `rotate` action is not of the form `row y=A by B` or `column x=A by B`.
+    InvalidRotation,
+    /// The action refers to pixels beyond the 50x6 screen.
+    OutOfRange,
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::UnknownAction   => "expected either 'rect' or 'rotate'",
+            ErrorKind::InvalidRect     => "expected the dimensions of the rectangle, such as '3x2'",
+            ErrorKind::InvalidRotation => "expected a rotation of either 'row y=A by B' or 'column x=A by B'",
+            ErrorKind::OutOfRange      => "expected the action to fit within the 50x6 screen",
+        })
+    }
+}
+
+/// Parses a single line of input, found at line `number`, into its `Action`.
+pub fn convert_to_action(number: usize, line: &str) -> Result<Action, ParseError<ErrorKind>> {
+    let error = |kind, text| ParseError::new(kind, number, line, text);
+    let elements = line.split_whitespace().collect::<Vec<&str>>();
+    let missing = &line[line.len()..];
+    match elements.first().cloned() {
+        Some("rect") => {
+            let dimensions = elements.get(1).cloned().unwrap_or(missing);
+            if let Some(&extra) = elements.get(2) { return Err(error(ErrorKind::InvalidRect, extra)); }
+            let mut values = dimensions.splitn(2, 'x').map(|x| x.parse::<u8>());
+            match (values.next(), values.next()) {
+                (Some(Ok(wide)), Some(Ok(tall))) if wide <= 50 && tall <= 6 => Ok(Action::Rect(wide, tall)),
+                (Some(Ok(_)), Some(Ok(_))) => Err(error(ErrorKind::OutOfRange, dimensions)),
+                _ => Err(error(ErrorKind::InvalidRect, dimensions)),
+            }
+        },
+        Some("rotate") => {
+            // A row is rotated around its 50 columns, while a column is rotated around its 6 rows.
+            let (prefix, limit, period) = match elements.get(1).cloned() {
+                Some("row")    => ("y=", 6, 50),
+                Some("column") => ("x=", 50, 6),
+                other => return Err(error(ErrorKind::InvalidRotation, other.unwrap_or(missing))),
+            };
+
+            let target = elements.get(2).cloned().unwrap_or(missing);
+            let x = match target.starts_with(prefix).then(|| target[2..].parse::<u8>()) {
+                Some(Ok(x)) if x < limit => x,
+                Some(Ok(_)) => return Err(error(ErrorKind::OutOfRange, target)),
+                _ => return Err(error(ErrorKind::InvalidRotation, target)),
+            };
+
+            match elements.get(3).cloned() {
+                Some("by") => (),
+                other => return Err(error(ErrorKind::InvalidRotation, other.unwrap_or(missing))),
+            }
+
+            let shift = elements.get(4).cloned().unwrap_or(missing);
+            let y = shift.parse::<u32>().map_err(|_| error(ErrorKind::InvalidRotation, shift))? % period;
+            let y = y as u8;
+            if let Some(&extra) = elements.get(5) { return Err(error(ErrorKind::InvalidRotation, extra)); }
+
+            if prefix == "y=" { Ok(Action::RotateRow(x, y)) } else { Ok(Action::RotateColumn(x, y)) }
+        },
+        other => Err(error(ErrorKind::UnknownAction, other.unwrap_or(missing))),
+    }
+}
+
+/// Parses every line of input into a list of `Action`s.
+pub fn parse_actions(input: &str) -> Result<Vec<Action>, ParseError<ErrorKind>> {
+    numbered_lines(input).map(|(number, line)| convert_to_action(number, line)).collect()
+}
+
+/// Applies every action to the given `screen`.
+pub fn apply_actions<S: Screen>(actions: &[Action], screen: &mut S) {
+    for action in actions {
+        match *action {
+            Action::Rect(wide, tall)            => screen.rect(wide, tall),
+            Action::RotateColumn(column, shift) => screen.rotate_column(column, shift),
+            Action::RotateRow(row, shift)       => screen.rotate_row(row, shift),
+        }
+    }
+}
+
+/// Counts the number of pixels that are lit after every action has been applied.
+pub fn lit_pixels<S: Screen>(actions: &[Action], mut screen: S) -> u32 {
+    apply_actions(actions, &mut screen);
+    screen.enabled_pixels()
+}
+
+/// Renders the screen after every action has been applied.
+pub fn render_pixels<S: Screen>(actions: &[Action], mut screen: S) -> String {
+    apply_actions(actions, &mut screen);
+    screen.render()
+}
+
+/// Counts the lit pixels on the screen, and renders the screen so that its code can be read.
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Parsed  = Vec<Action>;
+    type PartOne = u32;
+    type PartTwo = String;
+    type Error   = ErrorKind;
+
+    fn parse(input: &str) -> Result<Vec<Action>, ParseError<ErrorKind>> { parse_actions(input) }
+
+    fn part_one(actions: &Vec<Action>) -> u32 {
+        let mut screen = Screen64::default();
+        apply_actions(actions, &mut screen);
+        screen.enabled_pixels()
+    }
+
+    fn part_two(actions: &Vec<Action>) -> String {
+        let mut screen = Screen64::default();
+        apply_actions(actions, &mut screen);
+        screen.render()
+    }
+}
+
+#[test]
+fn test_rect() {
+    let mut screen = Screen8::default();
+    screen.rect(1, 1);
+    assert_eq!(screen.data[0], 0b1);
+    assert_eq!(screen.data[1], 0);
+    assert_eq!(screen.enabled_pixels(), 1);
+    screen.rect(1, 3);
+    assert_eq!(screen.data[0], 0b111);
+    assert_eq!(screen.data[1], 0);
+    assert_eq!(screen.enabled_pixels(), 3);
+    screen.rect(2, 2);
+    assert_eq!(screen.data[0], 0b111);
+    assert_eq!(screen.data[1], 0b11);
+    assert_eq!(screen.enabled_pixels(), 5);
+    screen.rect(3, 6);
+    assert_eq!(screen.data[0], 0b111111);
+    assert_eq!(screen.data[1], 0b111111);
+    assert_eq!(screen.data[2], 0b111111);
+    assert_eq!(screen.enabled_pixels(), 18);
+
+    let mut screen = Screen64::default();
+    screen.rect(3, 6);
+    for row in 0..6 { assert_eq!(screen.data[row], 0b111); }
+}
+
+#[test]
+fn test_rows() {
+    let mut screen = Screen8::default();
+    screen.rect(50, 1);
+    assert_eq!(screen.enabled_pixels(), 50);
+    for column in (0..50).step_by(2) { screen.rotate_column(column, 1); }
+    assert_eq!(screen.enabled_pixels(), 50);
+    assert_eq!(screen.data[0], 0b10);
+    assert_eq!(screen.data[1], 0b1);
+    screen.rotate_row(0, 1);
+    assert_eq!(screen.data[0], 0b11);
+    assert_eq!(screen.data[1], 0);
+    assert_eq!(screen.enabled_pixels(), 50);
+    screen.rect(50, 1);
+    assert_eq!(screen.data[0], 0b11);
+    assert_eq!(screen.data[1], 0b1);
+    assert_eq!(screen.enabled_pixels(), 75);
+    screen.rotate_column(0, 3);
+    screen.rotate_column(1, 3);
+    assert_eq!(screen.data[0], 0b11000);
+    assert_eq!(screen.data[1], 0b1000);
+}
+
+#[test]
+fn test_columns() {
+    let mut screen = Screen8::default();
+    screen.rect(30, 3);
+    assert_eq!(screen.data[0], 0b111);
+    assert_eq!(screen.data[30], 0);
+    assert_eq!(screen.enabled_pixels(), 90);
+    screen.rotate_column(0, 3);
+    assert_eq!(screen.data[0], 0b111000);
+    screen.rotate_column(0, 4); 
+    assert_eq!(screen.data[0], 0b001110);
+    screen.rotate_column(0, 3);
+    assert_eq!(screen.data[0], 0b110001); 
+    assert_eq!(screen.enabled_pixels(), 90);
+
+    let mut screen = Screen64::default();
+    screen.rect(3, 3);
+    for column in 0..3 { assert_eq!(screen.data[column], 7); }
+    screen.rotate_column(0, 3);
+    assert_eq!(screen.data[3], 1);
+}
+
+#[test]
+fn convert() {
+    let input = r#"rect 1x1
+    rotate row y=0 by 6
+    rect 1x1
+    rotate row y=0 by 3
+    rect 1x1
+    rotate row y=0 by 5
+    rect 1x1
+    rotate row y=0 by 4
+    rect 2x1"#;
+
+    let expected = [Action::Rect(1, 1), Action::RotateRow(0, 6), Action::Rect(1, 1),
+        Action::RotateRow(0, 3), Action::Rect(1, 1), Action::RotateRow(0, 5),
+        Action::Rect(1, 1), Action::RotateRow(0, 4), Action::Rect(2, 1)];
+
+    assert_eq!(&expected[..], &parse_actions(input).unwrap()[..]);
+}
+
+#[test]
+fn test_input() {
+    let inputs = include_str!("input.txt");
+    let number_of_actions = inputs.lines().count();
+    let inputs = parse_actions(inputs).unwrap();
+    assert_eq!(number_of_actions, inputs.len());
+
+    let input = r#"rect 1x1
+    rotate row y=0 by 6
+    rect 1x1
+    rotate row y=0 by 3
+    rect 1x1
+    rotate row y=0 by 5
+    rect 1x1
+    rotate row y=0 by 4
+    rect 2x1"#;
+    let input = parse_actions(input).unwrap();
+
+    assert_eq!(6, lit_pixels(&input, Screen8::default()));
+    assert_eq!(6, lit_pixels(&input, Screen64::default()));
+    assert_eq!(116, lit_pixels(&inputs, Screen8::default()));
+    assert_eq!(116, lit_pixels(&inputs, Screen64::default()));
+}
+
+#[test]
+fn parse_errors() {
+    let error = parse_actions("rect 3x2\nrotate row x=0 by 4").unwrap_err();
+    assert_eq!((ErrorKind::InvalidRotation, 2, 12, "x=0"), (error.kind, error.line, error.column, error.text.as_str()));
+    assert_eq!(ErrorKind::InvalidRect, parse_actions("rect 3by2").unwrap_err().kind);
+    assert_eq!(ErrorKind::OutOfRange, parse_actions("rect 3x7").unwrap_err().kind);
+    assert_eq!(ErrorKind::OutOfRange, parse_actions("rotate column x=50 by 1").unwrap_err().kind);
+    assert_eq!(ErrorKind::UnknownAction, parse_actions("blink").unwrap_err().kind);
+    assert_eq!(ErrorKind::InvalidRotation, parse_actions("rotate row y=1 by").unwrap_err().kind);
+}
+
+#[test]
+fn full_rotations() {
+    // Rotating by more than the length of a row or column wraps around it.
+    assert_eq!(Ok(Action::RotateRow(0, 10)), convert_to_action(1, "rotate row y=0 by 60"));
+    assert_eq!(Ok(Action::RotateColumn(0, 1)), convert_to_action(1, "rotate column x=0 by 13"));
+    assert_eq!(Ok(Action::RotateRow(5, 0)), convert_to_action(1, "rotate row y=5 by 1000"));
+
+    let actions = parse_actions("rect 2x1\nrotate row y=0 by 60\nrotate column x=0 by 13").unwrap();
+    assert_eq!(2, lit_pixels(&actions, Screen8::default()));
+    assert_eq!(2, lit_pixels(&actions, Screen64::default()));
+    assert_eq!(render_pixels(&actions, Screen8::default()), render_pixels(&actions, Screen64::default()));
+}
+
+#[test]
+fn mersenne_test() {
+    assert_eq!(7, mersenne_generator(3));
+}
@@ -0,0 +1,215 @@
+use self::DecompressionToken::{Marker, Regular};
+use parse::ParseError;
+use solution::Solution;
+use std::fmt::{self, Display};
+
+/// The base structure of the `Decompressor` `Iterator` contains the data as a
+/// string slice and keeps track of the current state via the `read` field.
+pub struct Decompressor<'a> {
+    data: &'a str,
+    read: usize,
+}
+
+impl<'a> Decompressor<'a> {
+    pub fn new(data: &'a str) -> Decompressor<'a> {
+        Decompressor { data: data, read: 0 }
+    }
+}
+
+/// A `DecompressionToken` may either be a regular string slice that does not
+/// need to be further searched, or a marker that contains the repeat count
+/// and the string slice to repeat, which may or may not contain even more
+/// `DecompressionToken`s.
+#[derive(PartialEq, Debug)]
+pub enum DecompressionToken<'a> {
+    Regular(&'a str),
+    Marker(u8, &'a str),
+}
+
+impl<'a> Iterator for Decompressor<'a> {
+    type Item = DecompressionToken<'a>;
+    fn next(&mut self) -> Option<DecompressionToken<'a>> {
+        // Determines if the next token to return will be a `Regular` or `Marker` token.
+        let found_marker = if let Some(character) = self.data.chars().nth(self.read) {
+            self.read += 1;
+            character == '('
+        } else {
+            return None
+        };
+
+        // Chooses the correct loop to execute accordingly.
+        if found_marker {
+            let (mut start, mut charas, mut found_first_num) = (self.read, 0, false);
+
+            // First find the number of characters that will be repeated before
+            // the first 'x', and then find the number of times to repeat
+            // before the ')' character that follows.
+            for character in self.data.chars().skip(self.read) {
+                if found_first_num {
+                    if character == ')' {
+                        let repeat = self.data[start..self.read].parse::<u8>().unwrap();
+                        start      = self.read + 1;
+                        self.read += charas + 1;
+                        return Some(Marker(repeat, &self.data[start..start + charas]));
+                    }
+                } else if character == 'x' {
+                    charas          = self.data[start..self.read].parse::<usize>().unwrap();
+                    found_first_num = true;
+                    start           = self.read + 1;
+                }
+                self.read += 1;
+            }
+            None // Error
+        } else {
+            let start = self.read - 1;
+
+            // A `Regular` token ends when either a '(' is found or the
+            // internal data has been exhausted.
+            for character in self.data.chars().skip(self.read) {
+                if character == '(' { return Some(Regular(&self.data[start..self.read])); }
+                self.read += 1;
+            }
+            Some(Regular(&self.data[start..]))
+        }
+    }
+}
+
+/// Calculates the size of the file if it was using version one of the format.
+pub fn calculate_size_p1(input: &str) -> usize {
+    let mut decompressed_length = 0;
+    for token in Decompressor::new(input) {
+        match token {
+            Marker(repeat, string) => decompressed_length += string.len() * repeat as usize,
+            Regular(string)        => decompressed_length += string.len(),
+        }
+    }
+    decompressed_length
+}
+
+/// Calculates the actual version two file size of the decompressed file.
+pub fn calculate_size_p2(input: &str) -> usize {
+    let mut decompressed_length = 0;
+    for token in Decompressor::new(input) {
+        match token {
+            Marker(repeat, string) => decompressed_length += calculate_size_p2(string) * repeat as usize,
+            Regular(string)        => decompressed_length += string.len(),
+        }
+    }
+    decompressed_length
+}
+
+/// Why the compressed file could not be parsed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// The file contains a character that is not ASCII.
+    NonAscii,
+    /// A marker is not of the form `(AxB)`, where `B` is no greater than 255.
+    InvalidMarker,
+    /// A marker repeats more characters than remain in the data that it belongs to.
+    MarkerOverrun,
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ErrorKind::NonAscii      => "expected only ASCII characters",
+            ErrorKind::InvalidMarker => "expected a marker such as '(3x3)', repeating no more than 255 times",
+            ErrorKind::MarkerOverrun => "expected the marker to repeat no more characters than follow it",
+        })
+    }
+}
+
+/// Ensures that every marker in the file is well-formed, including the markers that are nested within
+/// the data that another marker repeats. The file is expected to be a single line.
+pub fn validate(file: &str) -> Result<(), ParseError<ErrorKind>> {
+    if let Some((index, character)) = file.char_indices().find(|&(_, x)| !x.is_ascii()) {
+        return Err(ParseError::new(ErrorKind::NonAscii, 1, file, &file[index..index + character.len_utf8()]));
+    }
+
+    fn validate_markers(file: &str, data: &str) -> Result<(), ParseError<ErrorKind>> {
+        let mut read = 0;
+        while let Some(open) = data[read..].find('(') {
+            let start = read + open;
+            let marker = match data[start..].find(')') {
+                Some(close) => &data[start..start + close + 1],
+                None        => return Err(ParseError::new(ErrorKind::InvalidMarker, 1, file, &data[start..])),
+            };
+
+            let mut values = marker[1..marker.len() - 1].splitn(2, 'x');
+            let length = match (values.next().map(str::parse::<usize>), values.next().map(str::parse::<u8>)) {
+                (Some(Ok(length)), Some(Ok(_))) => length,
+                _ => return Err(ParseError::new(ErrorKind::InvalidMarker, 1, file, marker)),
+            };
+
+            let start = start + marker.len();
+            if length > data.len() - start {
+                return Err(ParseError::new(ErrorKind::MarkerOverrun, 1, file, marker));
+            }
+            validate_markers(file, &data[start..start + length])?;
+            read = start + length;
+        }
+        Ok(())
+    }
+
+    validate_markers(file, file)
+}
+
+/// Measures the decompressed length of the file with both versions of the format.
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Parsed  = String;
+    type PartOne = usize;
+    type PartTwo = usize;
+    type Error   = ErrorKind;
+
+    fn parse(input: &str) -> Result<String, ParseError<ErrorKind>> {
+        let file = input.trim();
+        validate(file).map(|_| String::from(file))
+    }
+
+    fn part_one(input: &String) -> usize { calculate_size_p1(input) }
+
+    fn part_two(input: &String) -> usize { calculate_size_p2(input) }
+}
+
+#[test]
+fn decompressor_test() {
+    let input = "ADVENTA(1x5)BC(3x3)XYZA(2x2)BCD(2x2)EFG(6x1)(1x3)AX(8x2)(3x3)ABCY";
+    let expected = [
+        DecompressionToken::Regular("ADVENTA"),
+        DecompressionToken::Marker(5, "B"),
+        DecompressionToken::Regular("C"),
+        DecompressionToken::Marker(3, "XYZ"),
+        DecompressionToken::Regular("A"),
+        DecompressionToken::Marker(2, "BC"),
+        DecompressionToken::Regular("D"),
+        DecompressionToken::Marker(2, "EF"),
+        DecompressionToken::Regular("G"),
+        DecompressionToken::Marker(1, "(1x3)A"),
+        DecompressionToken::Regular("X"),
+        DecompressionToken::Marker(2, "(3x3)ABC"),
+        DecompressionToken::Regular("Y")
+    ];
+
+    for (actual, expected) in Decompressor::new(input).zip(expected.iter()) {
+        assert_eq!(actual, *expected);
+    }
+
+    assert_eq!(Decompressor::new(input).count(), 13);
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(Ok(()), validate("X(8x2)(3x3)ABCY"));
+    let error = validate("A(1x5)BC(3x)XYZ").unwrap_err();
+    assert_eq!((ErrorKind::InvalidMarker, 9, "(3x)"), (error.kind, error.column, error.text.as_str()));
+    let error = validate("A(1x5)BC(3x3)XY").unwrap_err();
+    assert_eq!((ErrorKind::MarkerOverrun, 9, "(3x3)"), (error.kind, error.column, error.text.as_str()));
+    let error = validate("X(8x2)(3x3)AB").unwrap_err();
+    assert_eq!((ErrorKind::MarkerOverrun, 2, "(8x2)"), (error.kind, error.column, error.text.as_str()));
+    assert_eq!(ErrorKind::InvalidMarker, validate("(1x256)A").unwrap_err().kind);
+    assert_eq!(ErrorKind::InvalidMarker, validate("AB(1x").unwrap_err().kind);
+}
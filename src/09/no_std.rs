@@ -1,11 +1,10 @@
-#![no_std]
-#![feature(libc)]
-#![feature(start)]
-#![feature(alloc_system)]
-extern crate alloc_system;
+//! Solves day 9 without the standard library, providing the C `main` function directly and printing through
+//! `printf`. The `time` crate still links the standard library, which supplies the panic handler and libc.
+#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(test), no_main)]
 extern crate time;
-extern crate libc;
 
+#[cfg(not(test))]
 extern "C" {
     fn printf(fmt: *const u8, ...) -> i32;
 }
@@ -113,16 +112,17 @@ fn calculate_size_p2(input: &str) -> usize {
     decompressed_length
 }
 
-#[start]
-fn start(_argc: isize, _argv: *const *const u8) -> isize {
+#[cfg(not(test))]
+#[no_mangle]
+pub extern "C" fn main(_argc: i32, _argv: *const *const u8) -> i32 {
     let begin = time::precise_time_ns();
     let decompressed_length_p1 = calculate_size_p1(INPUT);
     let decompressed_length_p2 = calculate_size_p2(INPUT);
     let end = time::precise_time_ns();
     unsafe {
-        printf(b"The decompressed length of version one is %d bytes (%d KiB).\n\0".as_ptr(),
+        printf(b"The decompressed length of version one is %zu bytes (%zu KiB).\n\0".as_ptr(),
             decompressed_length_p1, decompressed_length_p1 / 1024);
-        printf(b"The decompressed length of version two is %lld bytes (%d GiB)\n\0".as_ptr(),
+        printf(b"The decompressed length of version two is %zu bytes (%zu GiB)\n\0".as_ptr(),
             decompressed_length_p2, decompressed_length_p2 / 1024 / 1024 / 1024);
         printf(b"Day 09: Execution Time: %f milliseconds\n\0".as_ptr(),
             ((end - begin) as f64) / 1_000_000f64);
@@ -154,4 +154,10 @@ fn decompressor_test() {
     }
 
     assert_eq!(Decompressor::new(input).count(), 13);
-}
\ No newline at end of file
+}
+
+#[test]
+fn decompressed_sizes() {
+    assert_eq!(70186, calculate_size_p1(INPUT));
+    assert_eq!(10915059201, calculate_size_p2(INPUT));
+}
@@ -0,0 +1,335 @@
+use parse::{numbered_lines, ParseError};
+use solution::Solution;
+use std::cmp::{min, max};
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// An `Instruction` given to the bots
+#[derive(Clone, Debug, PartialEq)]
+pub enum Instruction {
+    Take(u8, u8),                   // Value A goes to bot B
+    Give(u8, Recipient, Recipient)  // Bot A gives low to Recipient B and high to Recipient C
+}
+
+/// The `Recipient` of a value can either be a `Bin` or a `Bot`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Recipient { Bin(u8), Bot(u8) }
+
+/// Bots can only hold two values at a time: a left and right value.
+#[derive(Debug)]
+pub struct Bot { left: u8, right: u8 }
+
+impl Bot {
+    /// Determines if a bot is comparing the match that we are looking for.
+    fn contains_match(&self, cmp_left: u8, cmp_right: u8) -> bool {
+        self.right != self.left && (self.left == cmp_left || self.left == cmp_right) &&
+            (self.right == cmp_left || self.right == cmp_right) 
+    }
+}
+
+/// Why an instruction could not be parsed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// The line does not begin with `value` or `bot`.
+    UnknownInstruction,
+    /// The given word was expected, but a different word, or nothing, was found.
+    Expected(&'static str),
+    /// A value, bot, or output is not a number from 0 to 255.
+    InvalidNumber,
+    /// A recipient is neither a `bot` nor an `output`.
+    InvalidRecipient,
+    /// Text follows the end of the instruction.
+    TrailingText,
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ErrorKind::UnknownInstruction => f.write_str("expected the instruction to begin with 'value' or 'bot'"),
+            ErrorKind::Expected(word)     => write!(f, "expected '{}'", word),
+            ErrorKind::InvalidNumber      => f.write_str("expected a number from 0 to 255"),
+            ErrorKind::InvalidRecipient   => f.write_str("expected a recipient of either 'bot' or 'output'"),
+            ErrorKind::TrailingText       => f.write_str("expected the instruction to end"),
+        }
+    }
+}
+
+/// Why the bots could not finish following their instructions.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SolveError {
+    /// None of the given number of remaining instructions can ever be followed, as no bot that they name will
+    /// ever hold two chips.
+    Stalled(usize),
+    /// Every instruction was followed, but the output was never given a chip.
+    MissingOutput(u8),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SolveError::Stalled(count) => {
+                write!(f, "none of the {} remaining instructions can be followed, as no bot will hold two chips", count)
+            },
+            SolveError::MissingOutput(bin) => write!(f, "output {} was never given a chip", bin),
+        }
+    }
+}
+
+/// Take an unparsed `instruction`, found at line `number`, as a string slice and return the parsed
+/// `Instruction` values
+pub fn parse_instruction(number: usize, instruction: &str) -> Result<Instruction, ParseError<ErrorKind>> {
+    let commands = instruction.split_whitespace().collect::<Vec<&str>>();
+    let error    = |kind, text| ParseError::new(kind, number, instruction, text);
+    let command  = |id: usize| commands.get(id).cloned().unwrap_or(&instruction[instruction.len()..]);
+    let value    = |id: usize| command(id).parse::<u8>().map_err(|_| error(ErrorKind::InvalidNumber, command(id)));
+    let expect   = |id: usize, word: &'static str| {
+        if command(id) == word { Ok(()) } else { Err(error(ErrorKind::Expected(word), command(id))) }
+    };
+    let recipient = |id: usize| match command(id) {
+        "bot"    => value(id + 1).map(Recipient::Bot),
+        "output" => value(id + 1).map(Recipient::Bin),
+        other    => Err(error(ErrorKind::InvalidRecipient, other)),
+    };
+
+    let (instruction, length) = match command(0) {
+        "bot" => {
+            let id = value(1)?;
+            expect(2, "gives")?;
+            expect(3, "low")?;
+            expect(4, "to")?;
+            let low_recipient = recipient(5)?;
+            expect(7, "and")?;
+            expect(8, "high")?;
+            expect(9, "to")?;
+            let high_recipient = recipient(10)?;
+            (Instruction::Give(id, low_recipient, high_recipient), 12)
+        },
+        "value" => {
+            let value_id = value(1)?;
+            expect(2, "goes")?;
+            expect(3, "to")?;
+            expect(4, "bot")?;
+            (Instruction::Take(value_id, value(5)?), 6)
+        },
+        other => return Err(error(ErrorKind::UnknownInstruction, other)),
+    };
+
+    match commands.get(length) {
+        Some(extra) => Err(error(ErrorKind::TrailingText, extra)),
+        None        => Ok(instruction),
+    }
+}
+
+/// A list of instructions to process that is generated by parsing a string list of instructions.
+#[derive(Clone, Debug)]
+pub struct Instructions { data: VecDeque<Instruction> }
+
+impl FromStr for Instructions {
+    type Err = ParseError<ErrorKind>;
+    fn from_str(input: &str) -> Result<Instructions, ParseError<ErrorKind>> {
+        numbered_lines(input).map(|(number, line)| parse_instruction(number, line))
+            .collect::<Result<VecDeque<Instruction>, _>>().map(|data| Instructions { data: data })
+    }
+}
+
+/// Updates a bot's values in the given `$bots` `HashMap`.
+macro_rules! update_bot {
+    ($bots:ident, $bot_id:ident, $value:ident) => {{
+        if $bots.contains_key(&$bot_id) {
+            let bot = $bots.get_mut(&$bot_id).unwrap();
+            if bot.left == 0 { bot.left = $value; } else { bot.right = $value; }
+        } else {
+            let _ = $bots.insert($bot_id, Bot { left: $value, right: 0 });
+        }
+    }}
+}
+
+/// Computes which bot is responsible for comparing two set values and the product of
+/// bins 0, 1, and 2.
+pub fn calculate(bots: &mut HashMap<u8, Bot>, bin: &mut HashMap<u8, u8>, cmp_left: u8,
+    cmp_right: u8, mut instructions: Instructions) -> Result<(u8, u32), SolveError>
+{
+    let mut bot_compared = 0;
+    let mut bins_set     = 0;
+
+    // The number of instructions in a row that could not be followed, which once it reaches the length of the
+    // queue means that a full pass over the queue was made without any bot taking action.
+    let mut stalled = 0;
+
+    // Pop an instruction from the front
+    while let Some(instruction) = instructions.data.pop_front() {
+        let mut completed = false;
+
+        match instruction {
+            Instruction::Take(value, bot) => {
+                completed = true;
+
+                // If the key does not exist, add the key with the `left` value set to `value` and the
+                // `right` value as `0`. Otherwise, if the `left` value is `0` then set the `left` value to
+                // the `value`, else set the `right` value to `value`. Got that? Good.
+                let bot = bots.entry(bot).or_insert(Bot { left: 0, right: 0 });
+                if bot.left == 0 { bot.left = value; } else { bot.right = value; }
+            },
+            Instruction::Give(bot_id, low, high) => {
+                // These two `Option` values will contain the values that need to be changed after checking.
+                // This is done for safety to prevent the `bots` hashmap from being mutably borrowed twice.
+                let mut update_low_bot:  Option<(u8, u8)> = None;
+                let mut update_high_bot: Option<(u8, u8)> = None;
+
+                // Mutably borrow the bot that needs to transfer it's values. Note that we cannot mutably
+                // borrow more than once, so if another bot needs to be modified we will store that bot's ID
+                // and associated value in the `Option` variables above.
+                if let Some(bot) = bots.get_mut(&bot_id) {
+                    // Only if the bot is holding two values will the bot take action.
+                    if bot.left != 0 && bot.right != 0 {
+                        // If the bot's two values are what we are searching for, set the bot ID for return.
+                        if bot.contains_match(cmp_left, cmp_right) { bot_compared = bot_id; }
+
+                        completed     = true;
+                        let low_value  = min(bot.left, bot.right);
+                        let high_value = max(bot.left, bot.right);
+
+                        // Utilized to macth the low and high recipient's values to their respective places.
+                        // If a bot is to be set then that bot's ID and new value will be marked for later use.
+                        // If a bin is set that matches the bins we want, the `bins_set` value is increased.
+                        macro_rules! match_recipient {
+                            ($recipient:ident, $update:ident, $value:expr) => {{
+                                match $recipient {
+                                    Recipient::Bin(bin_id) => {
+                                        let _ = bin.insert(bin_id, $value);
+                                        if bin_id == 0 || bin_id == 1 || bin_id == 2 { bins_set += 1; }
+                                    },
+                                    Recipient::Bot(bot_id) => $update = Some((bot_id, $value))
+                                }
+                            }}
+                        }
+
+                        match_recipient!(low, update_low_bot, low_value);
+                        match_recipient!(high, update_high_bot, high_value);
+
+                        // The bot is now no longer holding a value.
+                        bot.left  = 0;
+                        bot.right = 0;
+                    }
+                }
+
+                // Update the value of the low bot if it needs to be updated.
+                if let Some((bot_id, value)) = update_low_bot { update_bot!(bots, bot_id, value); }
+
+                // Update the value of the high bot if it needs to be updated.
+                if let Some((bot_id, value)) = update_high_bot { update_bot!(bots, bot_id, value); }
+            },
+        }
+
+        // If the instruction was not used, push it to the back
+        if completed {
+            stalled = 0;
+        } else {
+            instructions.data.push_back(instruction);
+            stalled += 1;
+            if stalled == instructions.data.len() { return Err(SolveError::Stalled(stalled)) }
+        }
+
+        if bins_set == 3 { break }
+    }
+
+    let output = |id| bin.get(&id).map(|&value| value as u32).ok_or(SolveError::MissingOutput(id));
+    Ok((bot_compared, output(0)? * output(1)? * output(2)?))
+}
+
+/// Finds the bot that compares the value-61 and value-17 microchips, and the product of outputs 0, 1, and 2.
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Parsed  = Instructions;
+    type PartOne = u8;
+    type PartTwo = u32;
+    type Error   = ErrorKind;
+
+    fn parse(input: &str) -> Result<Instructions, ParseError<ErrorKind>> { input.parse::<Instructions>() }
+
+    fn part_one(instructions: &Instructions) -> u8 { solve(instructions).0 }
+
+    fn part_two(instructions: &Instructions) -> u32 { solve(instructions).1 }
+}
+
+/// Follows the instructions for the value-61 and value-17 microchips. As an answer can not be given when the
+/// bots can not finish, the reason is raised as a panic, which the runner reports as the reason that the part
+/// failed.
+fn solve(instructions: &Instructions) -> (u8, u32) {
+    match calculate(&mut HashMap::new(), &mut HashMap::new(), 61, 17, instructions.clone()) {
+        Ok(answer) => answer,
+        Err(why)   => panic!("{}", why),
+    }
+}
+
+#[test]
+fn bot_compare_test() {
+    let input = r#"value 5 goes to bot 2
+        bot 2 gives low to bot 1 and high to bot 0
+        value 3 goes to bot 1
+        bot 1 gives low to output 1 and high to bot 0
+        bot 0 gives low to output 2 and high to output 0
+        value 2 goes to bot 2"#;
+
+    let bots = &mut HashMap::new();
+    let bins = &mut HashMap::new();
+    let (bot, product) = calculate(bots, bins, 5, 2, input.parse().unwrap()).unwrap();
+    assert_eq!(2, bot);
+    assert_eq!(30, product);
+}
+
+#[test]
+fn unfinished() {
+    let calculate = |input: &str| calculate(&mut HashMap::new(), &mut HashMap::new(), 61, 17, input.parse().unwrap());
+    let stalled = "bot 1 gives low to output 0 and high to output 1";
+    assert_eq!(Err(SolveError::Stalled(1)), calculate(stalled));
+    let stalled = "value 3 goes to bot 1\nbot 1 gives low to output 0 and high to output 1\nbot 2 gives low to bot 1 \
+        and high to output 2";
+    assert_eq!(Err(SolveError::Stalled(2)), calculate(stalled));
+    let missing = "value 3 goes to bot 1\nvalue 5 goes to bot 1\nbot 1 gives low to output 0 and high to output 1";
+    assert_eq!(Err(SolveError::MissingOutput(2)), calculate(missing));
+
+    // Chips that are worth more than 40 no longer overflow the product.
+    let large = "value 200 goes to bot 1\nvalue 250 goes to bot 1\nvalue 240 goes to bot 2\nvalue 230 goes to bot 2\n\
+        bot 1 gives low to output 0 and high to output 1\nbot 2 gives low to output 2 and high to output 3";
+    assert_eq!(Ok((0, 200 * 250 * 230)), calculate(large));
+}
+
+#[test]
+fn parser_test() {
+    let input = r#"value 5 goes to bot 2
+        bot 2 gives low to bot 1 and high to bot 0
+        value 3 goes to bot 1
+        bot 1 gives low to output 1 and high to bot 0
+        bot 0 gives low to output 2 and high to output 0
+        value 2 goes to bot 2"#;
+
+    let expected = [
+        Instruction::Take(5, 2),
+        Instruction::Give(2, Recipient::Bot(1), Recipient::Bot(0)),
+        Instruction::Take(3, 1),
+        Instruction::Give(1, Recipient::Bin(1), Recipient::Bot(0)),
+        Instruction::Give(0, Recipient::Bin(2), Recipient::Bin(0)),
+        Instruction::Take(2, 2),
+    ];
+
+    let instructions = input.parse::<Instructions>().unwrap();
+    assert_eq!(&expected[..], &instructions.data.iter().cloned().collect::<Vec<_>>()[..]);
+}
+
+#[test]
+fn parse_errors() {
+    let error = parse_instruction(3, "bot 2 gives low to bin 1 and high to bot 0").unwrap_err();
+    assert_eq!((ErrorKind::InvalidRecipient, 3, 20, "bin"), (error.kind, error.line, error.column, error.text.as_str()));
+    let error = "value 5 goes to bot 2\nvalue 3 goes to bot".parse::<Instructions>().unwrap_err();
+    assert_eq!((ErrorKind::InvalidNumber, 2, 20), (error.kind, error.line, error.column));
+    assert_eq!(ErrorKind::Expected("goes"), parse_instruction(1, "value 5 went to bot 2").unwrap_err().kind);
+    assert_eq!(ErrorKind::InvalidNumber, parse_instruction(1, "value 256 goes to bot 2").unwrap_err().kind);
+    assert_eq!(ErrorKind::TrailingText, parse_instruction(1, "value 5 goes to bot 2 now").unwrap_err().kind);
+    assert_eq!(ErrorKind::UnknownInstruction, parse_instruction(1, "output 5").unwrap_err().kind);
+}
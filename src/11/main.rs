@@ -0,0 +1,14 @@
+extern crate advent_of_code_2016;
+
+use advent_of_code_2016::{input, Solution};
+use advent_of_code_2016::day11::Day11;
+use std::process::exit;
+
+fn main() {
+    let state = match input::parse_args::<Day11>() {
+        Ok(parsed) => parsed,
+        Err(why)   => { eprintln!("day_11: {}", why); exit(1); }
+    };
+    println!("Bringing every item to the fourth floor takes {} steps.", Day11::part_one(&state));
+    println!("With the elerium and dilithium items, it takes {} steps.", Day11::part_two(&state));
+}
@@ -0,0 +1,304 @@
+use parse::{numbered_lines, ParseError};
+use solution::Solution;
+use std::collections::{HashSet, VecDeque};
+use std::fmt::{self, Display};
+
+/// The number of floors in the facility, where the last floor is the assembling machine's floor.
+pub const FLOORS: usize = 4;
+
+/// The ordinals that each line of the input uses to name its floor.
+const ORDINALS: [&'static str; FLOORS] = ["first", "second", "third", "fourth"];
+
+/// The floors that an element's generator and microchip are on, counting from zero.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Pair { pub generator: usize, pub microchip: usize }
+
+/// An item that can be carried by the elevator, referring to the pair that it belongs to by index.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Item { Generator(usize), Microchip(usize) }
+
+/// The floor that the elevator is on, and the floors of every element's generator and microchip.
+///
+/// Elements are interchangeable, so states that only differ by which element is on which floors will take
+/// the same number of steps to complete. Keeping the pairs sorted collapses all of these equivalent states
+/// into one, which is what keeps the search small enough to finish in a reasonable time.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct State { pub elevator: usize, pub pairs: Vec<Pair> }
+
+impl State {
+    /// Creates a state with the elevator on the given floor, sorting the pairs into their canonical order.
+    pub fn new(elevator: usize, mut pairs: Vec<Pair>) -> State {
+        pairs.sort();
+        State { elevator: elevator, pairs: pairs }
+    }
+
+    /// A floor is safe when none of its microchips are left with another element's generator, unless that
+    /// microchip is connected to its own generator.
+    pub fn is_safe(&self) -> bool {
+        self.pairs.iter().filter(|pair| pair.microchip != pair.generator)
+            .all(|chip| self.pairs.iter().all(|pair| pair.generator != chip.microchip))
+    }
+
+    /// Every item has been brought to the top floor.
+    pub fn is_complete(&self) -> bool {
+        self.pairs.iter().all(|pair| pair.generator == FLOORS - 1 && pair.microchip == FLOORS - 1)
+    }
+
+    /// Collects the safe states that can be reached by carrying one or two items up or down a floor.
+    fn moves(&self) -> Vec<State> {
+        let items = self.pairs.iter().enumerate().flat_map(|(id, pair)| {
+            let generator = if pair.generator == self.elevator { Some(Item::Generator(id)) } else { None };
+            let microchip = if pair.microchip == self.elevator { Some(Item::Microchip(id)) } else { None };
+            generator.into_iter().chain(microchip)
+        }).collect::<Vec<Item>>();
+
+        // There is no reason to carry items down to floors that are already empty, along with every floor below.
+        let lowest = self.pairs.iter().map(|pair| pair.generator.min(pair.microchip)).min().unwrap_or(0);
+
+        let mut floors = Vec::with_capacity(2);
+        if self.elevator + 1 < FLOORS { floors.push(self.elevator + 1); }
+        if self.elevator > lowest { floors.push(self.elevator - 1); }
+
+        let mut states = Vec::new();
+        for &floor in &floors {
+            for (index, &first) in items.iter().enumerate() {
+                for &second in &items[index..] {
+                    let mut pairs = self.pairs.clone();
+                    for &item in &[first, second] {
+                        match item {
+                            Item::Generator(id) => pairs[id].generator = floor,
+                            Item::Microchip(id) => pairs[id].microchip = floor,
+                        }
+                    }
+
+                    let state = State::new(floor, pairs);
+                    if state.is_safe() { states.push(state); }
+                }
+            }
+        }
+        states
+    }
+}
+
+/// Performs a breadth-first search for the fewest steps that brings every item to the top floor.
+pub fn minimum_steps(initial: &State) -> Option<usize> {
+    let initial = State::new(initial.elevator, initial.pairs.clone());
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    visited.insert(initial.clone());
+    queue.push_back((initial, 0));
+
+    while let Some((state, steps)) = queue.pop_front() {
+        if state.is_complete() { return Some(steps) }
+        for next in state.moves() {
+            if !visited.contains(&next) {
+                visited.insert(next.clone());
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    None
+}
+
+/// The fewest steps that are needed to bring every item to the top floor, if there is any way to do so.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Steps(pub Option<usize>);
+
+impl Display for Steps {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(steps) => write!(f, "{}", steps),
+            None        => f.write_str("impossible"),
+        }
+    }
+}
+
+/// Why the description of the facility could not be parsed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// The given word was expected, but a different word, or nothing, was found.
+    Expected(&'static str),
+    /// An item is neither a `generator` nor a `microchip`.
+    UnknownItem,
+    /// The same element's generator or microchip was described twice.
+    DuplicateItem,
+    /// An element has a generator without a microchip, or a microchip without a generator.
+    UnpairedItem,
+    /// A microchip begins on a floor with another element's generator, but not its own.
+    UnsafeFloor,
+    /// Fewer than four floors were described.
+    MissingFloor,
+    /// More than four floors were described.
+    ExtraFloor,
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ErrorKind::Expected(word)  => write!(f, "expected '{}'", word),
+            ErrorKind::UnknownItem     => f.write_str("expected either a 'generator' or a 'microchip'"),
+            ErrorKind::DuplicateItem   => f.write_str("expected each generator and microchip to be described once"),
+            ErrorKind::UnpairedItem    => f.write_str("expected every element to have a generator and a microchip"),
+            ErrorKind::UnsafeFloor     => f.write_str("expected the microchip to be safe from other generators"),
+            ErrorKind::MissingFloor    => write!(f, "expected {} floors to be described", FLOORS),
+            ErrorKind::ExtraFloor      => write!(f, "expected no more than {} floors", FLOORS),
+        }
+    }
+}
+
+/// Where an item was described, so that errors found after every floor is parsed can still point to it.
+#[derive(Copy, Clone)]
+struct Origin<'a> { floor: usize, number: usize, line: &'a str, text: &'a str }
+
+/// Parses the description of each floor, such as
+/// `The first floor contains a hydrogen-compatible microchip and a lithium generator.`
+pub fn parse_facility(input: &str) -> Result<State, ParseError<ErrorKind>> {
+    // The generator and microchip of each element, in the order that each element was first described.
+    let mut elements: Vec<(&str, Option<Origin>, Option<Origin>)> = Vec::new();
+    let mut floors = 0;
+
+    for (number, line) in numbered_lines(input) {
+        let error = |kind, text| ParseError::new(kind, number, line, text);
+        if floors == FLOORS { return Err(error(ErrorKind::ExtraFloor, line)); }
+
+        let mut words = line.split_whitespace().map(|word| word.trim_end_matches(&[',', '.'][..]));
+        let mut next = || words.next().unwrap_or(&line[line.len()..]);
+        for &word in &["The", ORDINALS[floors], "floor", "contains"] {
+            let found = next();
+            if found != word { return Err(error(ErrorKind::Expected(word), found)); }
+        }
+
+        let mut word = next();
+        if word == "nothing" {
+            let found = next();
+            if found != "relevant" { return Err(error(ErrorKind::Expected("relevant"), found)); }
+            word = next();
+        } else {
+            loop {
+                if word == "and" { word = next(); }
+                if word != "a" { return Err(error(ErrorKind::Expected("a"), word)); }
+
+                let (element, kind) = (next(), next());
+                let (name, generator) = match kind {
+                    "generator" => (element, true),
+                    "microchip" => match element.len().checked_sub("-compatible".len()) {
+                        Some(end) if element.ends_with("-compatible") => (&element[..end], false),
+                        _ => return Err(error(ErrorKind::Expected("-compatible"), element)),
+                    },
+                    _ => return Err(error(ErrorKind::UnknownItem, kind)),
+                };
+
+                let index = match elements.iter().position(|&(existing, _, _)| existing == name) {
+                    Some(index) => index,
+                    None        => { elements.push((name, None, None)); elements.len() - 1 }
+                };
+
+                let origin = Origin { floor: floors, number: number, line: line, text: element };
+                let slot = if generator { &mut elements[index].1 } else { &mut elements[index].2 };
+                if slot.is_some() { return Err(error(ErrorKind::DuplicateItem, element)); }
+                *slot = Some(origin);
+
+                word = next();
+                if word.is_empty() { break }
+            }
+        }
+
+        if !word.is_empty() { return Err(error(ErrorKind::Expected("."), word)); }
+        floors += 1;
+    }
+
+    if floors < FLOORS {
+        let line = input.lines().last().unwrap_or("");
+        return Err(ParseError::new(ErrorKind::MissingFloor, floors.max(1), line, &line[line.len()..]));
+    }
+
+    let mut pairs = Vec::with_capacity(elements.len());
+    for &(_, generator, microchip) in &elements {
+        match (generator, microchip) {
+            (Some(generator), Some(microchip)) => pairs.push((generator, microchip)),
+            (Some(origin), None) | (None, Some(origin)) => {
+                return Err(ParseError::new(ErrorKind::UnpairedItem, origin.number, origin.line, origin.text));
+            },
+            (None, None) => unreachable!(),
+        }
+    }
+
+    for &(generator, microchip) in &pairs {
+        if generator.floor != microchip.floor && pairs.iter().any(|&(other, _)| other.floor == microchip.floor) {
+            return Err(ParseError::new(ErrorKind::UnsafeFloor, microchip.number, microchip.line, microchip.text));
+        }
+    }
+
+    Ok(State::new(0, pairs.iter().map(|&(generator, microchip)| {
+        Pair { generator: generator.floor, microchip: microchip.floor }
+    }).collect()))
+}
+
+/// Finds the fewest elevator steps that bring every generator and microchip to the fourth floor, first with
+/// the items that were described, and then with an elerium and a dilithium pair added to the first floor.
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Parsed  = State;
+    type PartOne = Steps;
+    type PartTwo = Steps;
+    type Error   = ErrorKind;
+
+    fn parse(input: &str) -> Result<State, ParseError<ErrorKind>> { parse_facility(input) }
+
+    fn part_one(state: &State) -> Steps { Steps(minimum_steps(state)) }
+
+    fn part_two(state: &State) -> Steps {
+        let mut pairs = state.pairs.clone();
+        pairs.extend_from_slice(&[Pair { generator: 0, microchip: 0 }; 2]);
+        Steps(minimum_steps(&State::new(state.elevator, pairs)))
+    }
+}
+
+#[test]
+fn part_one() {
+    let input = r#"The first floor contains a hydrogen-compatible microchip and a lithium-compatible microchip.
+The second floor contains a hydrogen generator.
+The third floor contains a lithium generator.
+The fourth floor contains nothing relevant."#;
+
+    let state = parse_facility(input).unwrap();
+    assert_eq!(vec![Pair { generator: 1, microchip: 0 }, Pair { generator: 2, microchip: 0 }], state.pairs);
+    assert_eq!(Some(11), minimum_steps(&state));
+    assert_eq!(Steps(Some(31)), Day11::part_one(&parse_facility(include_str!("input.txt")).unwrap()));
+}
+
+#[test]
+fn part_two() {
+    assert_eq!(Steps(Some(55)), Day11::part_two(&parse_facility(include_str!("input.txt")).unwrap()));
+}
+
+#[test]
+fn safety() {
+    assert!(State::new(0, vec![Pair { generator: 0, microchip: 0 }, Pair { generator: 0, microchip: 1 }]).is_safe());
+    assert!(!State::new(0, vec![Pair { generator: 0, microchip: 1 }, Pair { generator: 1, microchip: 0 }]).is_safe());
+}
+
+#[test]
+fn parse_errors() {
+    let floors = "The second floor contains nothing relevant.\nThe third floor contains nothing relevant.\n\
+        The fourth floor contains nothing relevant.";
+    let error = parse_facility(&format!("The first floor contains a hydrogen generator, and a lithium chip.\n{}",
+        floors)).unwrap_err();
+    assert_eq!((ErrorKind::UnknownItem, 1, 62, "chip"), (error.kind, error.line, error.column, error.text.as_str()));
+    let error = parse_facility(&format!("The first floor contains a hydrogen generator.\n{}", floors)).unwrap_err();
+    assert_eq!((ErrorKind::UnpairedItem, 1, 28, "hydrogen"), (error.kind, error.line, error.column, error.text.as_str()));
+    let error = parse_facility("The first floor contains nothing relevant.\nThe third floor contains nothing relevant.")
+        .unwrap_err();
+    assert_eq!((ErrorKind::Expected("second"), 2, 5), (error.kind, error.line, error.column));
+    assert_eq!(ErrorKind::MissingFloor, parse_facility("The first floor contains nothing relevant.").unwrap_err().kind);
+
+    let unsafe_floor = "The first floor contains a hydrogen-compatible microchip and a lithium generator.\n\
+        The second floor contains a hydrogen generator.\nThe third floor contains a lithium-compatible microchip.\n\
+        The fourth floor contains nothing relevant.";
+    assert_eq!(ErrorKind::UnsafeFloor, parse_facility(unsafe_floor).unwrap_err().kind);
+    assert_eq!(ErrorKind::DuplicateItem, parse_facility(&format!("The first floor contains a hydrogen generator \
+        and a hydrogen generator.\n{}", floors)).unwrap_err().kind);
+}
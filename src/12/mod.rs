@@ -0,0 +1,94 @@
+use assembunny::{parse_program, Compiled, ErrorKind, Machine, RegisterFile, RunOutcome, Tracer};
+use parse::ParseError;
+use solution::Solution;
+use std::fmt::{self, Display};
+
+/// Executes the program in `input` against the given `registers`, for no more than `budget` steps if a budget
+/// is given, returning how the run ended along with the value of register `a`. When a `tracer` is given, every
+/// instruction that is executed is recorded with it, which requires the program to be interpreted rather than
+/// compiled.
+pub fn calculate(registers: &[i64], input: &str, budget: Option<u64>, tracer: Option<&mut Tracer>)
+    -> Result<Answer, ParseError<ErrorKind>>
+{
+    let program = parse_program(input)?;
+    Ok(match tracer {
+        Some(tracer) => {
+            let mut machine = Machine::with_registers(program, registers);
+            let outcome = machine.run_bounded(budget, Some(tracer));
+            Answer(outcome, machine.registers()[0])
+        },
+        None => run(&Compiled::new(program), registers, budget),
+    })
+}
+
+fn run(compiled: &Compiled, registers: &[i64], budget: Option<u64>) -> Answer {
+    let (outcome, registers) = compiled.run(registers, budget);
+    Answer(outcome, registers[0])
+}
+
+/// The number of steps that the puzzle programs may execute before they are assumed to run forever.
+pub const BUDGET: u64 = 1_000_000_000;
+
+/// How the program finished, and the value that register `a` was left with.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Answer(pub RunOutcome, pub i64);
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            RunOutcome::Halted => write!(f, "{}", self.1),
+            outcome            => write!(f, "{} (a = {})", outcome, self.1),
+        }
+    }
+}
+
+/// The registers that are assigned before the program begins in the first part of the puzzle, which is none.
+pub const PART_ONE: &'static [(&'static str, i64)] = &[];
+
+/// The registers that are assigned before the program begins in the second part of the puzzle, where `c` is
+/// set to `1`.
+pub const PART_TWO: &'static [(&'static str, i64)] = &[("c", 1)];
+
+/// The initial value of every register, after the `assignments` of a part of the puzzle.
+pub fn initial(assignments: &[(&str, i64)]) -> Vec<i64> {
+    RegisterFile::default().values(assignments).expect("the puzzle only assigns to the default registers")
+}
+
+/// Runs the assembunny program with `c` set to `0`, and then set to `1`.
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Parsed  = Compiled;
+    type PartOne = Answer;
+    type PartTwo = Answer;
+    type Error   = ErrorKind;
+
+    /// Compiles the program once, so that both parts run the same bytecode.
+    fn parse(input: &str) -> Result<Compiled, ParseError<ErrorKind>> { parse_program(input).map(Compiled::new) }
+
+    fn part_one(compiled: &Compiled) -> Answer { run(compiled, &initial(PART_ONE), Some(BUDGET)) }
+
+    fn part_two(compiled: &Compiled) -> Answer { run(compiled, &initial(PART_TWO), Some(BUDGET)) }
+}
+
+#[test]
+fn part_one() {
+    let input = r#"cpy 41 a
+        inc a
+        inc a
+        dec a
+        jnz a 2
+        dec a"#;
+    let halted = Answer(RunOutcome::Halted, 42);
+    assert_eq!(Ok(halted), calculate(&initial(PART_ONE), input, None, None));
+
+    let mut tracer = Tracer::new();
+    assert_eq!(Ok(halted), calculate(&initial(PART_ONE), input, None, Some(&mut tracer)));
+    assert_eq!((5, 0), (tracer.steps(), tracer.count(5)));
+
+    assert_eq!(Ok(Answer(RunOutcome::BudgetExhausted, 41)), calculate(&initial(PART_ONE), input, Some(1), None));
+    assert_eq!("infinite loop detected (a = 1)", calculate(&initial(PART_ONE), "inc a\njnz 1 0", None, None).unwrap()
+        .to_string());
+}
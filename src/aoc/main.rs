@@ -0,0 +1,531 @@
+extern crate advent_of_code_2016;
+extern crate time;
+
+use advent_of_code_2016::Solution;
+use advent_of_code_2016::assembunny::debugger::{Command, Debugger};
+use advent_of_code_2016::assembunny::RegisterFile;
+use advent_of_code_2016::bench::{self, Baseline, BaselineError, Comparison, Config, Measurements, Phase};
+use advent_of_code_2016::input::Source;
+use advent_of_code_2016::output::{Format, Record};
+use advent_of_code_2016::verify::{Key, Manifest, Verdict};
+use advent_of_code_2016::day01::Day01;
+use advent_of_code_2016::day02::Day02;
+use advent_of_code_2016::day03::Day03;
+use advent_of_code_2016::day04::Day04;
+use advent_of_code_2016::day05::Day05;
+use advent_of_code_2016::day06::Day06;
+use advent_of_code_2016::day07::Day07;
+use advent_of_code_2016::day08::Day08;
+use advent_of_code_2016::day09::Day09;
+use advent_of_code_2016::day10::Day10;
+use advent_of_code_2016::day11::Day11;
+use advent_of_code_2016::day12::{self, Day12};
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::panic::{self, AssertUnwindSafe};
+use std::process::exit;
+
+const USAGE: &'static str = r#"Usage:
+    aoc run <DAY|all> [--part <1|2>] [--input <PATH|->] [--format <text|json|tsv>]
+    aoc verify <DAY|all> [--part <1|2>] [--input <PATH|->] [--answers <PATH>] [--record]
+    aoc bench <DAY|all> [--part <1|2>] [--input <PATH|->] [--iterations <N>] [--warmup <N>]
+              [--baseline <PATH>] [--save <PATH>] [--threshold <PERCENT>]
+    aoc debug <DAY> [--part <1|2>] [--input <PATH>]
+    aoc list
+    aoc help
+
+Input:
+    Each day reads its input from the --input path, or standard input when the path is '-'.
+    Otherwise, the AOC_INPUT_<DAY> environment variable (such as AOC_INPUT_07) is used if it
+    is set, followed by the input.txt file that is kept alongside the day's solution.
+
+Formats:
+    text  human-readable lines, such as 'Day 07 part 1: 118' (the default)
+    json  one {"day", "part", "answer", "elapsed_ns"} object per line
+    tsv   tab-separated day, part, answer, and elapsed_ns columns, after a header row;
+          newlines, tabs, and backslashes within an answer are escaped as \n, \t, and \\
+
+Verification:
+    Answers are compared against those recorded in the answers manifest (answers.tsv by
+    default) for the same day, part, and MD5 hash of the input. Each answer is reported as a
+    pass, a regression when it differs from the recorded answer, a failure when the solver
+    could not produce an answer, or as unrecorded. Passing --record saves the unrecorded
+    answers to the manifest.
+
+Benchmarks:
+    The parsing phase and each requested part are measured separately: every phase is run
+    --warmup times (1 by default) without being measured, and then --iterations times (10 by
+    default), reporting the minimum, median, and 95th percentile of the measured runs. Given a
+    --baseline file, each median is compared against the baseline's median for the same day and
+    phase, and is reported as a regression if it is more than --threshold percent (10 by
+    default) slower. Passing --save writes the measurements to a baseline file, keeping the
+    entries for any days and phases that were not measured.
+
+Debugging:
+    Days that run an assembunny program (day 12) can be stepped through interactively, starting
+    from the registers of the given part (part 1 by default). Each step shows the line that will
+    be executed next, alongside the optimized instruction that is executed in its place, such as
+    the 'NoOp's and 'Add' that replace an add loop. Enter 'help' at the prompt for the commands.
+
+Exit codes:
+    0  every requested solver finished, and no answer or benchmark regressed
+    1  a solver failed, its input could not be read or parsed, or an answer or benchmark regressed
+    2  the command line was invalid"#;
+
+/// Every requested solver ran to completion.
+const EXIT_SUCCESS: i32 = 0;
+/// At least one solver failed, or its input could not be read or parsed.
+const EXIT_FAILURE: i32 = 1;
+/// The arguments given to the runner were invalid.
+const EXIT_USAGE:   i32 = 2;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Part { One, Two }
+
+impl Part {
+    fn number(self) -> u8 { match self { Part::One => 1, Part::Two => 2 } }
+    fn phase(self) -> Phase { match self { Part::One => Phase::PartOne, Part::Two => Phase::PartTwo } }
+}
+
+/// A puzzle that the runner knows how to solve.
+struct Day {
+    id:    u8,
+    solve: fn(&str, Part) -> Result<String, String>,
+    bench: fn(&str, &[Phase], Config) -> Result<Measurements, String>,
+}
+
+const DAYS: &'static [Day] = &[
+    Day { id: Day01::DAY, solve: solve::<Day01>, bench: measure::<Day01> },
+    Day { id: Day02::DAY, solve: solve::<Day02>, bench: measure::<Day02> },
+    Day { id: Day03::DAY, solve: solve::<Day03>, bench: measure::<Day03> },
+    Day { id: Day04::DAY, solve: solve::<Day04>, bench: measure::<Day04> },
+    Day { id: Day05::DAY, solve: solve::<Day05>, bench: measure::<Day05> },
+    Day { id: Day06::DAY, solve: solve::<Day06>, bench: measure::<Day06> },
+    Day { id: Day07::DAY, solve: solve::<Day07>, bench: measure::<Day07> },
+    Day { id: Day08::DAY, solve: solve::<Day08>, bench: measure::<Day08> },
+    Day { id: Day09::DAY, solve: solve::<Day09>, bench: measure::<Day09> },
+    Day { id: Day10::DAY, solve: solve::<Day10>, bench: measure::<Day10> },
+    Day { id: Day11::DAY, solve: solve::<Day11>, bench: measure::<Day11> },
+    Day { id: Day12::DAY, solve: solve::<Day12>, bench: measure::<Day12> },
+];
+
+/// Parses the input and solves the requested part of the puzzle, formatting the answer for display, or
+/// describing where the input is malformed if it could not be parsed.
+fn solve<S: Solution>(input: &str, part: Part) -> Result<String, String> {
+    let parsed = S::parse(input).map_err(|why| why.diagnose(input))?;
+    Ok(match part {
+        Part::One => S::part_one(&parsed).to_string(),
+        Part::Two => S::part_two(&parsed).to_string(),
+    })
+}
+
+/// Benchmarks the requested phases of the puzzle, or describes where the input is malformed if it could
+/// not be parsed.
+fn measure<S: Solution>(input: &str, phases: &[Phase], config: Config) -> Result<Measurements, String> {
+    bench::benchmark::<S>(input, phases, config).map_err(|why| why.diagnose(input))
+}
+
+/// Why a solver could not produce an answer.
+enum Failure {
+    /// The input could not be parsed, for the described reason.
+    Parse(String),
+    /// The solver panicked, with the described reason.
+    Panic(String),
+}
+
+/// Runs `solver`, catching any panic that it raises without letting the default hook print the panic and its
+/// backtrace, and returning the panic's message instead so that it may be reported as a diagnostic.
+fn quietly<T, F: FnOnce() -> T>(solver: F) -> Result<T, String> {
+    let hook   = panic::take_hook();
+    panic::set_hook(Box::new(|_| ()));
+    let result = panic::catch_unwind(AssertUnwindSafe(solver));
+    panic::set_hook(hook);
+    result.map_err(|payload| {
+        match payload.downcast_ref::<&'static str>() {
+            Some(message) => message.to_string(),
+            None => payload.downcast_ref::<String>().cloned().unwrap_or_else(|| String::from("the solver panicked")),
+        }
+    })
+}
+
+/// The options given to the `run`, `verify`, and `bench` subcommands.
+struct Options<'a> {
+    days:      Vec<&'a Day>,
+    parts:     Vec<Part>,
+    input:     Option<String>,
+    format:    Format,
+    answers:   PathBuf,
+    record:    bool,
+    config:    Config,
+    baseline:  Option<PathBuf>,
+    save:      Option<PathBuf>,
+    threshold: f64,
+}
+
+fn find_day(id: &str) -> Result<&'static Day, String> {
+    match id.parse::<u8>() {
+        Ok(id) => DAYS.iter().find(|day| day.id == id).ok_or_else(|| format!("there is no solver for day {}", id)),
+        Err(_) => Err(format!("'{}' is not a day", id)),
+    }
+}
+
+fn parse_options<I: Iterator<Item = String>>(command: &str, mut args: I) -> Result<Options<'static>, String> {
+    let days = match args.next() {
+        Some(ref day) if day == "all" => DAYS.iter().collect(),
+        Some(day) => vec![find_day(&day)?],
+        None      => return Err(format!("{} requires a day, or 'all'", command)),
+    };
+
+    let mut options = Options {
+        days:      days,
+        parts:     vec![Part::One, Part::Two],
+        input:     None,
+        format:    Format::Text,
+        answers:   Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.tsv"),
+        record:    false,
+        config:    Config::default(),
+        baseline:  None,
+        save:      None,
+        threshold: 10.0,
+    };
+
+    while let Some(argument) = args.next() {
+        match argument.as_str() {
+            "--part" | "-p" => options.parts = match args.next().as_deref() {
+                Some("1") => vec![Part::One],
+                Some("2") => vec![Part::Two],
+                _         => return Err(String::from("--part must be either 1 or 2")),
+            },
+            "--input" | "-i" => options.input = match args.next() {
+                Some(path) => Some(path),
+                None       => return Err(String::from("--input requires a path, or '-' for standard input")),
+            },
+            "--format" | "-f" if command == "run" => options.format = match args.next() {
+                Some(format) => format.parse::<Format>()?,
+                None         => return Err(String::from("--format requires either text, json, or tsv")),
+            },
+            "--answers" | "-a" if command == "verify" => options.answers = match args.next() {
+                Some(path) => PathBuf::from(path),
+                None       => return Err(String::from("--answers requires a path")),
+            },
+            "--record" if command == "verify" => options.record = true,
+            "--iterations" | "-n" if command == "bench" => options.config.iterations = match args.next() {
+                Some(ref count) => match count.parse::<usize>() {
+                    Ok(count) if count != 0 => count,
+                    _ => return Err(format!("'{}' is not a positive number of iterations", count)),
+                },
+                None => return Err(String::from("--iterations requires a number")),
+            },
+            "--warmup" | "-w" if command == "bench" => options.config.warmup = match args.next() {
+                Some(ref count) => count.parse::<usize>()
+                    .map_err(|_| format!("'{}' is not a number of warmup runs", count))?,
+                None => return Err(String::from("--warmup requires a number")),
+            },
+            "--baseline" | "-b" if command == "bench" => options.baseline = match args.next() {
+                Some(path) => Some(PathBuf::from(path)),
+                None       => return Err(String::from("--baseline requires a path")),
+            },
+            "--save" if command == "bench" => options.save = match args.next() {
+                Some(path) => Some(PathBuf::from(path)),
+                None       => return Err(String::from("--save requires a path")),
+            },
+            "--threshold" | "-t" if command == "bench" => options.threshold = match args.next() {
+                Some(ref percent) => match percent.parse::<f64>() {
+                    Ok(percent) if percent >= 0.0 => percent,
+                    _ => return Err(format!("'{}' is not a percentage", percent)),
+                },
+                None => return Err(String::from("--threshold requires a percentage")),
+            },
+            _ => return Err(format!("unknown argument '{}'", argument)),
+        }
+    }
+
+    if options.input.is_some() && options.days.len() != 1 {
+        return Err(String::from("--input may only be used with a single day"));
+    }
+
+    Ok(options)
+}
+
+/// Reads the input for a `day`, reporting why the input could not be read if it fails.
+fn read_input(day: &Day, options: &Options) -> Option<String> {
+    match Source::for_day(day.id, options.input.as_deref()).read() {
+        Ok(input) => Some(input),
+        Err(why)  => { eprintln!("aoc: day {:02}: {}", day.id, why); None }
+    }
+}
+
+/// Solves one part of a day's puzzle, timing the solver and catching any panic that it raises.
+fn solve_part(day: &Day, input: &str, part: Part) -> Result<Record, Failure> {
+    let begin  = time::precise_time_ns();
+    let answer = quietly(|| (day.solve)(input, part));
+    let end    = time::precise_time_ns();
+    match answer {
+        Ok(Ok(answer)) => Ok(Record { day: day.id, part: part.number(), answer: answer, elapsed_ns: end - begin }),
+        Ok(Err(why))   => Err(Failure::Parse(why)),
+        Err(why)       => Err(Failure::Panic(why)),
+    }
+}
+
+fn run(options: Options) -> i32 {
+    if let Some(header) = options.format.header() { println!("{}", header); }
+
+    let mut status = EXIT_SUCCESS;
+    for day in &options.days {
+        let input = match read_input(day, &options) {
+            Some(input) => input,
+            None        => { status = EXIT_FAILURE; continue }
+        };
+
+        for &part in &options.parts {
+            match solve_part(day, &input, part) {
+                Ok(record) => println!("{}", record.format(options.format)),
+                Err(Failure::Parse(why)) => {
+                    // Every part shares the same input, so the remaining parts would fail in the same way.
+                    eprintln!("aoc: day {:02}: {}", day.id, why);
+                    status = EXIT_FAILURE;
+                    break
+                },
+                Err(Failure::Panic(why)) => {
+                    eprintln!("aoc: day {:02} part {} failed: {}", day.id, part.number(), why);
+                    status = EXIT_FAILURE;
+                }
+            }
+        }
+    }
+    status
+}
+
+/// Displays an answer on the same line if it fits on one, or on the lines that follow otherwise.
+fn inline(answer: &str) -> String {
+    if answer.contains('\n') { format!("\n{}", answer.trim_end()) } else { format!(" {}", answer) }
+}
+
+fn verify(options: Options) -> i32 {
+    let mut manifest = match Manifest::load(&options.answers) {
+        Ok(manifest) => manifest,
+        Err(why)     => { eprintln!("aoc: {}", why); return EXIT_FAILURE; }
+    };
+
+    let (mut passed, mut regressed, mut failed, mut unrecorded, mut recorded) = (0, 0, 0, 0, 0);
+    for day in &options.days {
+        let input = read_input(day, &options);
+        let mut reported = false;
+        for &part in &options.parts {
+            let record = match input.as_ref().map(|input| solve_part(day, input, part)) {
+                Some(Ok(record)) => record,
+                failure => {
+                    match failure {
+                        Some(Err(Failure::Parse(why))) => {
+                            if !reported { eprintln!("aoc: day {:02}: {}", day.id, why); }
+                            reported = true;
+                        },
+                        Some(Err(Failure::Panic(why))) => {
+                            eprintln!("aoc: day {:02} part {} failed: {}", day.id, part.number(), why);
+                        },
+                        _ => (),
+                    }
+                    println!("Day {:02} part {}: FAIL", day.id, part.number());
+                    failed += 1;
+                    continue
+                }
+            };
+
+            let key = Key::new(day.id, part.number(), input.as_ref().unwrap());
+            match manifest.check(&key, &record.answer) {
+                Verdict::Pass => {
+                    println!("Day {:02} part {}: pass", day.id, part.number());
+                    passed += 1;
+                },
+                Verdict::Regression { expected } => {
+                    println!("Day {:02} part {}: REGRESSION\n  expected:{}\n  actual:{}", day.id, part.number(),
+                        inline(&expected), inline(&record.answer));
+                    regressed += 1;
+                },
+                Verdict::Unrecorded if options.record => {
+                    println!("Day {:02} part {}: recorded{}", day.id, part.number(), inline(&record.answer));
+                    manifest.record(key, record.answer);
+                    recorded += 1;
+                },
+                Verdict::Unrecorded => {
+                    println!("Day {:02} part {}: unrecorded{}", day.id, part.number(), inline(&record.answer));
+                    unrecorded += 1;
+                }
+            }
+        }
+    }
+
+    println!("{} passed, {} regressed, {} failed, {} unrecorded, {} recorded",
+        passed, regressed, failed, unrecorded, recorded);
+
+    if recorded != 0 {
+        if let Err(why) = manifest.save(&options.answers) {
+            eprintln!("aoc: unable to save '{}': {}", options.answers.display(), why);
+            return EXIT_FAILURE;
+        }
+    }
+
+    if regressed == 0 && failed == 0 { EXIT_SUCCESS } else { EXIT_FAILURE }
+}
+
+/// Formats a duration in nanoseconds as milliseconds.
+fn milliseconds(nanoseconds: u64) -> String { format!("{:.3} ms", nanoseconds as f64 / 1_000_000f64) }
+
+fn bench(options: Options) -> i32 {
+    let baseline = match options.baseline {
+        Some(ref path) => match Baseline::load(path) {
+            Ok(baseline) => Some(baseline),
+            Err(why)     => { eprintln!("aoc: {}", why); return EXIT_FAILURE; }
+        },
+        None => None,
+    };
+
+    let mut saved = match options.save {
+        Some(ref path) => match Baseline::load(path) {
+            Ok(saved) => Some(saved),
+            Err(BaselineError::Io(_, ref why)) if why.kind() == io::ErrorKind::NotFound => Some(Baseline::default()),
+            Err(why) => { eprintln!("aoc: {}", why); return EXIT_FAILURE; }
+        },
+        None => None,
+    };
+
+    let mut phases = vec![Phase::Parse];
+    phases.extend(options.parts.iter().map(|part| part.phase()));
+
+    let mut status = EXIT_SUCCESS;
+    for day in &options.days {
+        let input = match read_input(day, &options) {
+            Some(input) => input,
+            None        => { status = EXIT_FAILURE; continue }
+        };
+
+        let results = quietly(|| (day.bench)(&input, &phases, options.config));
+        let results = match results {
+            Ok(Ok(results)) => results,
+            Ok(Err(why)) => {
+                eprintln!("aoc: day {:02}: {}", day.id, why);
+                status = EXIT_FAILURE;
+                continue
+            },
+            Err(why) => {
+                eprintln!("aoc: day {:02} failed: {}", day.id, why);
+                status = EXIT_FAILURE;
+                continue
+            }
+        };
+
+        for (phase, summary) in results {
+            let comparison = match baseline {
+                Some(ref baseline) => match baseline.compare(day.id, phase, &summary, options.threshold) {
+                    Comparison::New => String::from("  (not in baseline)"),
+                    Comparison::Within(change) => format!("  {:+.1}%", change),
+                    Comparison::Regressed(change) => {
+                        status = EXIT_FAILURE;
+                        format!("  {:+.1}% REGRESSION", change)
+                    }
+                },
+                None => String::new(),
+            };
+
+            println!("Day {:02} {:<5}  min {:>12}  median {:>12}  p95 {:>12}{}", day.id, phase,
+                milliseconds(summary.min), milliseconds(summary.median), milliseconds(summary.p95), comparison);
+
+            if let Some(ref mut saved) = saved { saved.insert(day.id, phase, summary); }
+        }
+    }
+
+    if let (Some(saved), Some(path)) = (saved, options.save) {
+        if let Err(why) = saved.save(&path) {
+            eprintln!("aoc: unable to save '{}': {}", path.display(), why);
+            return EXIT_FAILURE;
+        }
+    }
+
+    status
+}
+
+fn debug(options: Options) -> i32 {
+    let day = match options.days[..] {
+        [day] if day.id == Day12::DAY => day,
+        [day] => { eprintln!("aoc: day {:02} does not run an assembunny program", day.id); return EXIT_USAGE; },
+        _     => { eprintln!("aoc: debug requires a single day"); return EXIT_USAGE; },
+    };
+
+    if options.input.as_deref() == Some("-") {
+        eprintln!("aoc: the program cannot be read from standard input, which is used for commands");
+        return EXIT_USAGE;
+    }
+
+    let input = match read_input(day, &options) {
+        Some(input) => input,
+        None        => return EXIT_FAILURE,
+    };
+
+    let registers = day12::initial(if options.parts == [Part::Two] { day12::PART_TWO } else { day12::PART_ONE });
+    let mut debugger = match Debugger::new(&input, &RegisterFile::default(), &registers) {
+        Ok(debugger) => debugger,
+        Err(why)     => { eprintln!("aoc: day {:02}: {}", day.id, why.diagnose(&input)); return EXIT_FAILURE; }
+    };
+
+    println!("{}\n{}", debugger.location(), debugger.registers());
+    let (stdin, stdout) = (io::stdin(), io::stdout());
+    let mut lines = stdin.lock().lines();
+    let mut last = Command::Step(1);
+    loop {
+        print!("(debug) ");
+        let _ = stdout.lock().flush();
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            _ => break,
+        };
+
+        let command = if line.trim().is_empty() { Ok(last) } else { Command::parse(&line, debugger.register_file()) };
+        match command {
+            Ok(Command::Quit) => break,
+            Ok(command) => {
+                println!("{}", debugger.execute(command));
+                last = command;
+            },
+            Err(why) => println!("{}", why),
+        }
+    }
+    EXIT_SUCCESS
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let status = match args.next().as_deref() {
+        Some(command @ "run") | Some(command @ "verify") | Some(command @ "bench") | Some(command @ "debug") => {
+            match parse_options(command, args) {
+                Ok(options) => match command {
+                    "run"    => run(options),
+                    "verify" => verify(options),
+                    "bench"  => bench(options),
+                    _        => debug(options),
+                },
+                Err(why) => {
+                    eprintln!("aoc: {}\n\n{}", why, USAGE);
+                    EXIT_USAGE
+                }
+            }
+        },
+        Some("list") => {
+            for day in DAYS { println!("{:02}", day.id); }
+            EXIT_SUCCESS
+        },
+        Some("help") | Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            EXIT_SUCCESS
+        },
+        Some(command) => {
+            eprintln!("aoc: unknown command '{}'\n\n{}", command, USAGE);
+            EXIT_USAGE
+        },
+        None => {
+            eprintln!("{}", USAGE);
+            EXIT_USAGE
+        }
+    };
+    exit(status);
+}
@@ -0,0 +1,359 @@
+//! Finds mistakes in a program before it is run, by splitting the program into basic blocks that are joined
+//! into a control-flow graph, which can be written as Graphviz DOT.
+//!
+//! The program is analyzed as it was written. A program that toggles its own instructions may take paths that
+//! the graph does not show, and a jump whose offset is read from a register may land on any line, so every
+//! line of a program with such a jump begins its own block, which may be reached from that jump.
+use super::{Instruction, Kind, RegisterFile};
+use std::collections::VecDeque;
+
+/// A run of instructions that is only ever entered at its first instruction, and only ever left after its
+/// last instruction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Block {
+    /// The index of the first instruction of the block.
+    pub start:      usize,
+    /// The index after the last instruction of the block.
+    pub end:        usize,
+    /// Where the program may continue once the block has been executed.
+    pub successors: Vec<(Target, Branch)>,
+    /// Whether the block can be reached from the first instruction.
+    pub reachable:  bool,
+}
+
+/// Where the program continues after a block.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Target {
+    /// The block at the given index.
+    Block(usize),
+    /// Past the last instruction, which halts the program.
+    Halt,
+    /// Before the first instruction, which also halts the program.
+    Escape,
+}
+
+/// Why the program continues to a successor.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Branch {
+    /// The block always continues to the successor.
+    Always,
+    /// The jump that ends the block was taken.
+    Taken,
+    /// The jump that ends the block was not taken.
+    NotTaken,
+}
+
+/// A mistake that was found within a program, where each line is referred to by the index of its instruction.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Lint {
+    /// The lines from the first index up to the second can never be executed.
+    Unreachable(usize, usize),
+    /// The jump at the index targets the given line, which is outside of the program. Jumping to the line
+    /// just after the last one is not considered a mistake, as that is how a program halts.
+    JumpOutOfRange(usize, i64),
+    /// The instruction at the index reads the register before the register has been assigned on every path
+    /// that leads to it. As counting up or down from zero is common, `inc` and `dec` are not considered to
+    /// read their register.
+    UseBeforeAssignment(usize, usize),
+}
+
+impl Lint {
+    /// The index of the line that the mistake was found at.
+    pub fn pc(&self) -> usize {
+        match *self {
+            Lint::Unreachable(pc, _) | Lint::JumpOutOfRange(pc, _) | Lint::UseBeforeAssignment(pc, _) => pc,
+        }
+    }
+
+    /// Describes the mistake, naming registers from the `registers` that the program was parsed with.
+    pub fn describe(&self, registers: &RegisterFile) -> String {
+        match *self {
+            Lint::Unreachable(start, end) if end - start == 1 => format!("line {} is unreachable", start + 1),
+            Lint::Unreachable(start, end) => format!("lines {}-{} are unreachable", start + 1, end),
+            Lint::JumpOutOfRange(pc, target) => {
+                format!("line {}: jumps to line {}, which is outside of the program", pc + 1, target + 1)
+            },
+            Lint::UseBeforeAssignment(pc, register) => {
+                format!("line {}: reads {} before it is assigned", pc + 1, registers.name(register))
+            },
+        }
+    }
+}
+
+/// The control-flow graph of a program, along with every mistake that was found within it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Analysis {
+    pub blocks: Vec<Block>,
+    pub lints:  Vec<Lint>,
+}
+
+/// The registers that an instruction reads, and then the registers that it writes.
+fn accesses(instruction: &Instruction) -> (Vec<usize>, Vec<usize>) {
+    let register = |kind| match kind { Kind::Register(x) => vec![x], Kind::Value(_) => Vec::new() };
+    match *instruction {
+        Instruction::Add(x, y) => (vec![x, y], vec![x, y]),
+        Instruction::Multiply(x, y, scratch, total) => {
+            let mut reads = register(x);
+            reads.extend_from_slice(&[y, total]);
+            (reads, vec![y, scratch, total])
+        },
+        Instruction::CopyInteger(_, y)  => (Vec::new(), vec![y]),
+        Instruction::CopyRegister(x, y) => (vec![x], vec![y]),
+        Instruction::Increment(x) | Instruction::Decrement(x) => (Vec::new(), vec![x]),
+        Instruction::JumpIf(condition, offset) => {
+            let mut reads = register(condition);
+            reads.extend(register(offset));
+            (reads, Vec::new())
+        },
+        Instruction::Toggle(x) | Instruction::Out(x) => (register(x), Vec::new()),
+        Instruction::Skip(_) | Instruction::NoOp => (Vec::new(), Vec::new()),
+    }
+}
+
+/// Builds the control-flow graph of the `program`, which was parsed with the given `registers`, and lints it,
+/// where the `inputs` are the registers that are assigned before the program begins.
+pub fn analyze(program: &[Instruction], registers: &RegisterFile, inputs: &[usize]) -> Analysis {
+    let length = program.len();
+    if length == 0 { return Analysis { blocks: Vec::new(), lints: Vec::new() } }
+
+    let dynamic = program.iter().any(|instruction| match *instruction {
+        Instruction::JumpIf(Kind::Value(0), _) => false,
+        Instruction::JumpIf(_, Kind::Register(_)) => true,
+        _ => false,
+    });
+
+    // A block begins at the first instruction, at every target of a jump, and after every jump.
+    let mut leaders = vec![dynamic; length];
+    leaders[0] = true;
+    let mut lints = Vec::new();
+    for (pc, instruction) in program.iter().enumerate() {
+        if let Instruction::JumpIf(condition, offset) = *instruction {
+            if pc + 1 < length { leaders[pc + 1] = true; }
+            if let (true, Kind::Value(offset)) = (condition != Kind::Value(0), offset) {
+                let target = (pc as i64).saturating_add(offset);
+                if target < 0 || target > length as i64 {
+                    lints.push(Lint::JumpOutOfRange(pc, target));
+                } else if target < length as i64 {
+                    leaders[target as usize] = true;
+                }
+            }
+        }
+    }
+
+    let starts = (0..length).filter(|&pc| leaders[pc]).collect::<Vec<usize>>();
+    let mut block_of = vec![0; length];
+    for (block, &start) in starts.iter().enumerate() {
+        let end = starts.get(block + 1).cloned().unwrap_or(length);
+        for entry in &mut block_of[start..end] { *entry = block; }
+    }
+
+    let at = |pc: i64| -> Target {
+        if pc < 0 {
+            Target::Escape
+        } else if pc >= length as i64 {
+            Target::Halt
+        } else {
+            Target::Block(block_of[pc as usize])
+        }
+    };
+
+    let mut blocks = starts.iter().enumerate().map(|(block, &start)| {
+        let end = starts.get(block + 1).cloned().unwrap_or(length);
+        let last = end - 1;
+        let next = at(end as i64);
+        let successors = match program[last] {
+            Instruction::JumpIf(Kind::Value(0), _) => vec![(next, Branch::Always)],
+            Instruction::JumpIf(condition, offset) => {
+                let jumps = match offset {
+                    Kind::Value(offset) => vec![at((last as i64).saturating_add(offset))],
+                    Kind::Register(_) => {
+                        let mut targets = (0..starts.len()).map(Target::Block).collect::<Vec<Target>>();
+                        targets.push(Target::Halt);
+                        targets
+                    },
+                };
+                match condition {
+                    Kind::Value(_) => jumps.into_iter().map(|target| (target, Branch::Always)).collect(),
+                    Kind::Register(_) => {
+                        let mut successors = jumps.into_iter().map(|target| (target, Branch::Taken))
+                            .collect::<Vec<(Target, Branch)>>();
+                        successors.push((next, Branch::NotTaken));
+                        successors
+                    },
+                }
+            },
+            _ => vec![(next, Branch::Always)],
+        };
+        Block { start: start, end: end, successors: successors, reachable: false }
+    }).collect::<Vec<Block>>();
+
+    // Every block that can be reached from the first block.
+    let mut queue = VecDeque::new();
+    blocks[0].reachable = true;
+    queue.push_back(0);
+    while let Some(block) = queue.pop_front() {
+        for (target, _) in blocks[block].successors.clone() {
+            if let Target::Block(next) = target {
+                if !blocks[next].reachable {
+                    blocks[next].reachable = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+
+    let mut block = 0;
+    while block < blocks.len() {
+        if blocks[block].reachable { block += 1; continue }
+        let start = blocks[block].start;
+        while block < blocks.len() && !blocks[block].reachable { block += 1; }
+        lints.push(Lint::Unreachable(start, blocks.get(block).map_or(length, |block| block.start)));
+    }
+
+    // The registers that are certainly assigned when each block begins, found by intersecting the registers
+    // that are assigned at the end of each of its predecessors, until nothing changes.
+    let count = registers.len();
+
+    let mut entry = vec![vec![true; count]; blocks.len()];
+    entry[0] = vec![false; count];
+    for &input in inputs { entry[0][input] = true; }
+
+    let assigned = |entry: &[bool], block: &Block| -> Vec<bool> {
+        let mut assigned = entry.to_vec();
+        for instruction in &program[block.start..block.end] {
+            for register in accesses(instruction).1 { assigned[register] = true; }
+        }
+        assigned
+    };
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (index, block) in blocks.iter().enumerate().filter(|&(_, block)| block.reachable) {
+            let exit = assigned(&entry[index], block);
+            for &(target, _) in &block.successors {
+                if let Target::Block(next) = target {
+                    for register in 0..count {
+                        if entry[next][register] && !exit[register] {
+                            entry[next][register] = false;
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    for (index, block) in blocks.iter().enumerate().filter(|&(_, block)| block.reachable) {
+        let mut assigned = entry[index].clone();
+        for (pc, instruction) in program.iter().enumerate().take(block.end).skip(block.start) {
+            let (reads, writes) = accesses(instruction);
+            for register in reads {
+                if !assigned[register] {
+                    lints.push(Lint::UseBeforeAssignment(pc, register));
+                    assigned[register] = true;
+                }
+            }
+            for register in writes { assigned[register] = true; }
+        }
+    }
+
+    lints.sort_by_key(|lint| lint.pc());
+    Analysis { blocks: blocks, lints: lints }
+}
+
+impl Analysis {
+    /// Writes the control-flow graph of the `program` that was analyzed as a Graphviz DOT digraph, where each
+    /// block lists its lines, naming registers from the `registers`, and unreachable blocks are dashed.
+    pub fn to_dot(&self, program: &[Instruction], registers: &RegisterFile) -> String {
+        let mut output = String::from("digraph program {\n    node [shape=box, fontname=\"monospace\"];\n");
+        for (index, block) in self.blocks.iter().enumerate() {
+            let lines = (block.start..block.end).map(|pc| format!("{}: {}\\l", pc + 1, program[pc].named(registers)))
+                .collect::<String>();
+            let style = if block.reachable { "" } else { ", style=dashed" };
+            output.push_str(&format!("    b{} [label=\"{}\"{}];\n", index, lines, style));
+        }
+
+        let successors = self.blocks.iter().flat_map(|block| block.successors.iter());
+        if successors.clone().any(|&(target, _)| target == Target::Halt) {
+            output.push_str("    halt [shape=doublecircle];\n");
+        }
+        if successors.clone().any(|&(target, _)| target == Target::Escape) {
+            output.push_str("    escape [shape=doublecircle, label=\"out of range\"];\n");
+        }
+
+        for (index, block) in self.blocks.iter().enumerate() {
+            for &(target, branch) in &block.successors {
+                let target = match target {
+                    Target::Block(block) => format!("b{}", block),
+                    Target::Halt         => String::from("halt"),
+                    Target::Escape       => String::from("escape"),
+                };
+                let label = match branch {
+                    Branch::Always   => "",
+                    Branch::Taken    => " [label=\"taken\"]",
+                    Branch::NotTaken => " [label=\"not taken\"]",
+                };
+                output.push_str(&format!("    b{} -> {}{};\n", index, target, label));
+            }
+        }
+        output.push_str("}\n");
+        output
+    }
+}
+
+#[test]
+fn blocks() {
+    let registers = RegisterFile::default();
+    let program = super::parse_program("cpy 3 b\ninc a\ndec b\njnz b -2\ndec a").unwrap();
+    let analysis = analyze(&program, &registers, &[]);
+    assert_eq!(vec![(0, 1), (1, 4), (4, 5)], analysis.blocks.iter().map(|block| (block.start, block.end))
+        .collect::<Vec<(usize, usize)>>());
+    assert_eq!(vec![(Target::Block(1), Branch::Taken), (Target::Block(2), Branch::NotTaken)],
+        analysis.blocks[1].successors);
+    assert_eq!(vec![(Target::Halt, Branch::Always)], analysis.blocks[2].successors);
+    assert!(analysis.lints.is_empty());
+
+    let dot = analysis.to_dot(&program, &registers);
+    assert!(dot.contains("    b1 [label=\"2: inc a\\l3: dec b\\l4: jnz b -2\\l\"];\n"));
+    assert!(dot.contains("    b1 -> b1 [label=\"taken\"];\n    b1 -> b2 [label=\"not taken\"];\n"));
+    assert!(dot.contains("    b2 -> halt;\n"));
+}
+
+#[test]
+fn lints() {
+    let registers = RegisterFile::default();
+    let program = super::parse_program("cpy c a\njnz 1 3\ninc b\ninc b\njnz a -9\nout d\njnz 1 3").unwrap();
+    let analysis = analyze(&program, &registers, &[]);
+    assert_eq!(vec![
+        Lint::UseBeforeAssignment(0, 2),
+        Lint::Unreachable(2, 4),
+        Lint::JumpOutOfRange(4, -5),
+        Lint::UseBeforeAssignment(5, 3),
+        Lint::JumpOutOfRange(6, 9),
+    ], analysis.lints);
+    assert_eq!("lines 3-4 are unreachable", analysis.lints[1].describe(&registers));
+    assert_eq!("line 5: jumps to line -4, which is outside of the program", analysis.lints[2].describe(&registers));
+    assert_eq!("line 6: reads d before it is assigned", analysis.lints[3].describe(&registers));
+    let dot = analysis.to_dot(&program, &registers);
+    assert!(dot.contains("    escape [shape=doublecircle, label=\"out of range\"];\n"));
+
+    // A register that is only assigned on one path may still be unassigned.
+    let program = super::parse_program("jnz a 2\ncpy 1 b\nout b\njnz 1 9223372036854775807").unwrap();
+    let far = Lint::JumpOutOfRange(3, i64::MAX);
+    assert_eq!(vec![Lint::UseBeforeAssignment(0, 0), Lint::UseBeforeAssignment(2, 1), far],
+        analyze(&program, &registers, &[]).lints);
+    assert_eq!(vec![Lint::UseBeforeAssignment(2, 1), far], analyze(&program, &registers, &[0]).lints);
+
+    // Day 12 reads `c`, which is given as an input in the second part.
+    let program = super::parse_program(include_str!("../12/input.txt")).unwrap();
+    assert!(analyze(&program, &registers, &[0, 1, 2, 3]).lints.is_empty());
+
+    // Programs for other registers are described with the names of those registers.
+    let registers = RegisterFile::new(&["x", "y", "z", "w", "acc"]).unwrap();
+    let program = super::parse_program_with("inc acc\nout w", &registers).unwrap();
+    let analysis = analyze(&program, &registers, &[]);
+    assert_eq!(vec![Lint::UseBeforeAssignment(1, 3)], analysis.lints);
+    assert_eq!("line 2: reads w before it is assigned", analysis.lints[0].describe(&registers));
+    assert!(analysis.to_dot(&program, &registers).contains("    b0 [label=\"1: inc acc\\l2: out w\\l\"];\n"));
+}
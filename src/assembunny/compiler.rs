@@ -0,0 +1,255 @@
+//! Compiles an assembunny program once into a compact bytecode, which can then be run any number of times
+//! with different initial registers, without parsing or optimizing the program again.
+//!
+//! The bytecode resolves every jump with a constant offset to the index that it lands on, turns jumps whose
+//! condition is a constant into either an unconditional jump or nothing at all, and drops the `NoOp`s that
+//! the optimizer leaves in place of a loop. As `tgl` rewrites the program while it runs, programs that
+//! contain it are not compiled, and are instead run by a `Machine`.
+use super::{optimize, Instruction, Kind, Machine, Program, RunOutcome};
+use super::machine::jump;
+
+/// A single bytecode instruction, where every jump target is the index of another bytecode instruction.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Op {
+    Add(usize, usize),
+    Multiply(Kind, usize, usize, usize),
+    Set(i64, usize),
+    Copy(usize, usize),
+    Increment(usize),
+    Decrement(usize),
+    /// Jumps to the target, which begins at the given line of the program, where a target past the last
+    /// instruction halts the program.
+    Jump(usize, usize),
+    /// Jumps to the target, which begins at the given line, if the register is not zero.
+    JumpIf(usize, usize, usize),
+    /// Jumps by the offset from the instruction at the given line of the program if the condition is not
+    /// zero, where either the offset is only known at runtime, or the jump may leave before the first line.
+    JumpBy(Kind, Kind, usize),
+    /// A constant jump before the first instruction.
+    Escape,
+}
+
+/// A program that has been compiled once, and may then be run against any initial registers.
+#[derive(Clone, Debug)]
+pub struct Compiled {
+    program: Program,
+    /// The bytecode, unless the program contains a `tgl`.
+    ops:     Option<Vec<Op>>,
+    /// The index of the bytecode instruction that each line of the program begins at.
+    lines:   Vec<usize>,
+    /// The line of the program that each bytecode instruction was compiled from.
+    origins: Vec<usize>,
+}
+
+impl Compiled {
+    /// Optimizes the `program` and compiles it to bytecode, if it contains no `tgl`.
+    pub fn new(program: Program) -> Compiled {
+        if program.iter().any(|instruction| matches!(*instruction, Instruction::Toggle(_))) {
+            return Compiled { program: program, ops: None, lines: Vec::new(), origins: Vec::new() };
+        }
+
+        let optimized = optimize(&program);
+
+        // The bytecode instruction that each line begins at, where a dropped line begins at the next one.
+        let mut lines = Vec::with_capacity(optimized.len() + 1);
+        let mut length = 0;
+        for instruction in &optimized {
+            lines.push(length);
+            if !skipped(instruction) { length += 1; }
+        }
+        lines.push(length);
+
+        let target = |pc: usize, offset: i64| -> Op {
+            if offset < 0 {
+                pc.checked_sub(offset.unsigned_abs() as usize).map_or(Op::Escape, |pc| Op::Jump(lines[pc], pc))
+            } else {
+                let line = pc.saturating_add(offset as usize).min(optimized.len());
+                Op::Jump(lines[line], line)
+            }
+        };
+
+        let compiled = optimized.iter().enumerate().filter(|&(_, instruction)| !skipped(instruction));
+        let origins = compiled.clone().map(|(pc, _)| pc).collect();
+        let ops = compiled.map(|(pc, instruction)| match *instruction {
+                Instruction::Add(x, y)                      => Op::Add(x, y),
+                Instruction::Multiply(x, y, scratch, total) => Op::Multiply(x, y, scratch, total),
+                Instruction::CopyInteger(value, register)   => Op::Set(value, register),
+                Instruction::CopyRegister(x, y)             => Op::Copy(x, y),
+                Instruction::Increment(register)            => Op::Increment(register),
+                Instruction::Decrement(register)            => Op::Decrement(register),
+                Instruction::JumpIf(Kind::Value(_), Kind::Value(offset)) => target(pc, offset),
+                Instruction::JumpIf(Kind::Register(x), Kind::Value(offset)) => match target(pc, offset) {
+                    Op::Jump(target, line) => Op::JumpIf(x, target, line),
+                    _                => Op::JumpBy(Kind::Register(x), Kind::Value(offset), pc),
+                },
+                Instruction::JumpIf(condition, offset)      => Op::JumpBy(condition, offset, pc),
+                _ => unreachable!("skipped instructions are filtered out"),
+            }).collect();
+
+        Compiled { program: program, ops: Some(ops), lines: lines, origins: origins }
+    }
+
+    /// The program that was compiled.
+    pub fn program(&self) -> &[Instruction] { &self.program }
+
+    /// Whether the program was compiled to bytecode, rather than being run by a `Machine`.
+    pub fn is_compiled(&self) -> bool { self.ops.is_some() }
+
+    /// Runs the program from its first instruction with the given `registers`, as `Machine::run_bounded`
+    /// would, returning how the run ended along with the final registers. The `budget` counts the lines of the
+    /// optimized program that a machine would execute, so the `NoOp`s and `out`s that were dropped are counted
+    /// as though they were executed, and the same budget ends both runs at the same point.
+    ///
+    /// A loop may only be entered by a jump, so the state of the program is only compared against the saved
+    /// state after each jump that is taken, which finds the same loops without slowing down every other step.
+    pub fn run(&self, registers: &[i64], budget: Option<u64>) -> (RunOutcome, Vec<i64>) {
+        let ops = match self.ops {
+            Some(ref ops) => ops,
+            None => {
+                let mut machine = Machine::with_registers(self.program.clone(), registers);
+                let outcome = machine.run_bounded(budget, None);
+                return (outcome, machine.registers().to_vec());
+            }
+        };
+
+        // Each line from the `entry` line up to the line of the next bytecode instruction is executed.
+        let mut registers = registers.to_vec();
+        let (mut pc, mut steps, mut entry) = (0, 0, 0);
+        let mut saved = (pc, registers.clone());
+        let (mut power, mut length) = (1, 0);
+        while let Some(&op) = ops.get(pc) {
+            let line = self.origins[pc];
+            steps += (line + 1 - entry) as u64;
+            if budget.is_some_and(|budget| steps > budget) { return (RunOutcome::BudgetExhausted, registers) }
+            entry = line + 1;
+
+            let target = match op {
+                Op::Add(x, y) => { registers[y] = registers[y].wrapping_add(registers[x]); registers[x] = 0; None },
+                Op::Multiply(x, y, scratch, total) => {
+                    let x = match x { Kind::Register(x) => registers[x], Kind::Value(x) => x };
+                    registers[total] = registers[total].wrapping_add(x.wrapping_mul(registers[y]));
+                    registers[scratch] = 0;
+                    registers[y] = 0;
+                    None
+                },
+                Op::Set(value, register) => { registers[register] = value; None },
+                Op::Copy(x, y)           => { registers[y] = registers[x]; None },
+                Op::Increment(register)  => { registers[register] = registers[register].wrapping_add(1); None },
+                Op::Decrement(register)  => { registers[register] = registers[register].wrapping_sub(1); None },
+                Op::Jump(target, line)   => { entry = line; Some(target) },
+                Op::JumpIf(x, target, line) => if registers[x] != 0 { entry = line; Some(target) } else { None },
+                Op::Escape               => return (RunOutcome::JumpOutOfRange, registers),
+                Op::JumpBy(condition, offset, line) => {
+                    let value = |kind| match kind { Kind::Register(x) => registers[x], Kind::Value(x) => x };
+                    if value(condition) == 0 {
+                        None
+                    } else {
+                        match jump(line, value(offset)) {
+                            Some(line) => {
+                                entry = line.min(self.lines.len() - 1);
+                                Some(self.lines[entry])
+                            },
+                            None => return (RunOutcome::JumpOutOfRange, registers),
+                        }
+                    }
+                },
+            };
+
+            match target {
+                Some(target) => {
+                    pc = target;
+                    if pc == saved.0 && registers == saved.1 { return (RunOutcome::LoopDetected, registers) }
+                    length += 1;
+                    if length == power {
+                        saved = (pc, registers.clone());
+                        power *= 2;
+                        length = 0;
+                    }
+                },
+                None => pc += 1,
+            }
+        }
+
+        // The lines that were dropped from the end of the program are executed before it halts. A machine takes
+        // one more step to find that it has halted after an `out` on the last line, as `out` reports its output.
+        let mut remaining = (self.lines.len() - 1).saturating_sub(entry) as u64;
+        if remaining != 0 && matches!(self.program.last(), Some(&Instruction::Out(_))) { remaining += 1; }
+        if budget.is_some_and(|budget| steps + remaining > budget) { return (RunOutcome::BudgetExhausted, registers) }
+        (RunOutcome::Halted, registers)
+    }
+}
+
+/// Whether an instruction does nothing, and so has no bytecode of its own.
+fn skipped(instruction: &Instruction) -> bool {
+    matches!(*instruction, Instruction::NoOp | Instruction::Skip(_) | Instruction::Out(_)
+        | Instruction::JumpIf(Kind::Value(0), _))
+}
+
+#[test]
+fn compiled() {
+    let samples: &[(&str, [i64; 4])] = &[
+        ("cpy 41 a\ninc a\ninc a\ndec a\njnz a 2\ndec a", [0; 4]),
+        (include_str!("../12/input.txt"), [0, 0, 1, 0]),
+        ("cpy 3 b\ncpy 4 d\ncpy b c\ninc a\ndec c\njnz c -2\ndec d\njnz d -5", [5, 0, 0, 0]),
+        ("cpy 2 b\njnz 0 5\ninc a\ndec b\njnz b -2\nout a\njnz b 9", [0; 4]),
+        ("cpy 3 b\ninc a\njnz a b\ninc a\ninc a\ncpy -4 c\ndec b\njnz b c", [0; 4]),
+        ("inc a\njnz a -5\ninc a", [0; 4]),
+        ("inc a\ncpy 5 b\ndec b\ncpy 5 b\njnz 1 -2", [0; 4]),
+        ("jnz 1 0", [0; 4]),
+        ("cpy 2 a\ntgl a\ntgl a\ntgl a\ncpy 1 a\ndec a\ndec a", [0; 4]),
+    ];
+
+    for &(source, registers) in samples {
+        let program = super::parse_program(source).unwrap();
+        let compiled = Compiled::new(program.clone());
+        assert_eq!(!source.contains("tgl"), compiled.is_compiled(), "{}", source);
+
+        // A loop may be noticed at a different point within it, and so with different registers.
+        let mut machine = Machine::with_registers(program, &registers);
+        let outcome = machine.run_bounded(None, None);
+        let (compiled_outcome, compiled_registers) = compiled.run(&registers, None);
+        assert_eq!(outcome, compiled_outcome, "{}", source);
+        if outcome != RunOutcome::LoopDetected {
+            assert_eq!(machine.registers(), &compiled_registers[..], "{}", source);
+        }
+    }
+
+    let compiled = Compiled::new(super::parse_program("inc a\njnz 1 -1").unwrap());
+    assert_eq!((RunOutcome::BudgetExhausted, vec![3, 0, 0, 0]), compiled.run(&[0; 4], Some(6)));
+
+    // A budget counts the same steps as it does for the machine, including the lines that were dropped.
+    let samples = ["cpy 3 b\ninc a\ndec b\njnz b -2\nout a\njnz 0 4\ninc a\njnz a 2\ndec a\nout a\nout b",
+        "cpy 2 b\njnz 1 2\nout a\nout b\ninc a\ndec b\njnz b -3\nout a"];
+    for source in &samples {
+        let program = super::parse_program(source).unwrap();
+        let compiled = Compiled::new(program.clone());
+        for budget in 0..24 {
+            let mut machine = Machine::new(program.clone());
+            let outcome = machine.run_bounded(Some(budget), None);
+            let message = format!("{} with a budget of {}", source, budget);
+            assert_eq!((outcome, machine.registers().to_vec()), compiled.run(&[0; 4], Some(budget)), "{}", message);
+        }
+    }
+}
+
+/// Compares the compiled program against the interpreter on the day 12 input, and is ignored by default as it
+/// is a benchmark. Run it with `cargo test --release -- --ignored compiled_speedup --nocapture`.
+#[test]
+#[ignore]
+fn compiled_speedup() {
+    use bench::{measure, Config};
+
+    let config = Config { warmup: 3, iterations: 50 };
+    let program = super::parse_program(include_str!("../12/input.txt")).unwrap();
+    let compiled = Compiled::new(program.clone());
+
+    // Both are built before they are measured, so that only running the program is timed.
+    let mut machine = Machine::new(program);
+    let interpreted = measure(config, || {
+        machine.reset(&[0, 0, 1, 0]);
+        machine.run_bounded(None, None)
+    });
+    let compiled = measure(config, || compiled.run(&[0, 0, 1, 0], None));
+    println!("interpreted: {:?}\ncompiled:    {:?}", interpreted, compiled);
+    assert!(compiled.median < interpreted.median);
+}
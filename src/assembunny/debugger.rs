@@ -0,0 +1,337 @@
+//! An interactive debugger, which steps through a program while showing each line of the source alongside
+//! the optimized instruction that is executed in its place.
+use super::{parse_program_with, ErrorKind, Machine, RegisterFile, Status};
+use parse::ParseError;
+use std::fmt::{self, Display};
+
+/// Text that describes every command that the debugger accepts.
+pub const HELP: &'static str = r#"Commands:
+    step [N]                 execute the next N instructions (1 by default)
+    continue                 execute until a breakpoint is reached, or the program halts
+    break <LINE>             stop before executing the given line
+    break <REGISTER> <OP> <VALUE>
+                             stop once a register compares to a value, where OP is one of
+                             ==, !=, <, <=, >, or >=
+    delete <N>               remove the Nth breakpoint
+    breakpoints              list every breakpoint
+    registers                show the value of every register
+    list                     show the program, marking the next line to be executed
+    help                     show this message
+    quit                     stop debugging
+Commands may be abbreviated to their first letter, other than breakpoints, which is abbreviated
+to 'i'. An empty line repeats the last command."#;
+
+/// A comparison between the value of a register and a constant.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Comparison { Equal, NotEqual, Less, LessOrEqual, Greater, GreaterOrEqual }
+
+impl Comparison {
+    fn holds(self, left: i64, right: i64) -> bool {
+        match self {
+            Comparison::Equal          => left == right,
+            Comparison::NotEqual       => left != right,
+            Comparison::Less           => left < right,
+            Comparison::LessOrEqual    => left <= right,
+            Comparison::Greater        => left > right,
+            Comparison::GreaterOrEqual => left >= right,
+        }
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Comparison::Equal          => "==",
+            Comparison::NotEqual       => "!=",
+            Comparison::Less           => "<",
+            Comparison::LessOrEqual    => "<=",
+            Comparison::Greater        => ">",
+            Comparison::GreaterOrEqual => ">=",
+        })
+    }
+}
+
+/// Where the debugger should stop executing the program.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Breakpoint {
+    /// Stop before executing the line, counting from one.
+    Line(usize),
+    /// Stop once the register, referred to by its index, compares to the value.
+    Condition(usize, Comparison, i64),
+}
+
+impl Breakpoint {
+    /// Describes the breakpoint, such as `line 3` or `c >= 4`, naming its register from the `registers`.
+    pub fn describe(&self, registers: &RegisterFile) -> String {
+        match *self {
+            Breakpoint::Line(line) => format!("line {}", line),
+            Breakpoint::Condition(register, comparison, value) => {
+                format!("{} {} {}", registers.name(register), comparison, value)
+            }
+        }
+    }
+}
+
+/// A command that was given to the debugger.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Command {
+    Step(usize),
+    Continue,
+    Break(Breakpoint),
+    Delete(usize),
+    Breakpoints,
+    Registers,
+    List,
+    Help,
+    Quit,
+}
+
+/// Why a command could not be parsed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CommandError {
+    /// The command is not one that the debugger knows.
+    Unknown(String),
+    /// The command was given invalid arguments, where the usage of the command is given.
+    Usage(&'static str),
+}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CommandError::Unknown(ref command) => write!(f, "unknown command '{}', try 'help'", command),
+            CommandError::Usage(usage)         => write!(f, "usage: {}", usage),
+        }
+    }
+}
+
+/// How the `break` command is used.
+const BREAK: &'static str = "break <LINE>, or break <REGISTER> <OP> <VALUE>";
+
+impl Command {
+    /// Parses a command, where the conditions of breakpoints may refer to any of the given `registers`.
+    pub fn parse(input: &str, registers: &RegisterFile) -> Result<Command, CommandError> {
+        let arguments = input.split_whitespace().collect::<Vec<&str>>();
+        let usage = |usage| Err(CommandError::Usage(usage));
+        match (arguments.first().cloned().unwrap_or(""), &arguments[1..]) {
+            ("step", arguments) | ("s", arguments) => match *arguments {
+                []      => Ok(Command::Step(1)),
+                [count] => count.parse::<usize>().map(Command::Step).or_else(|_| usage("step [N]")),
+                _       => usage("step [N]"),
+            },
+            ("continue", []) | ("c", []) => Ok(Command::Continue),
+            ("break", arguments) | ("b", arguments) => match *arguments {
+                [line] => match line.parse::<usize>() {
+                    Ok(line) if line != 0 => Ok(Command::Break(Breakpoint::Line(line))),
+                    _ => usage(BREAK),
+                },
+                [register, comparison, value] => {
+                    let register = match registers.index(register) {
+                        Some(register) => register,
+                        None           => return usage(BREAK),
+                    };
+                    let comparison = match comparison {
+                        "==" => Comparison::Equal,
+                        "!=" => Comparison::NotEqual,
+                        "<"  => Comparison::Less,
+                        "<=" => Comparison::LessOrEqual,
+                        ">"  => Comparison::Greater,
+                        ">=" => Comparison::GreaterOrEqual,
+                        _    => return usage(BREAK),
+                    };
+                    match value.parse::<i64>() {
+                        Ok(value) => Ok(Command::Break(Breakpoint::Condition(register, comparison, value))),
+                        Err(_)    => usage(BREAK),
+                    }
+                },
+                _ => usage(BREAK),
+            },
+            ("delete", arguments) | ("d", arguments) => match *arguments {
+                [index] => match index.parse::<usize>() {
+                    Ok(index) if index != 0 => Ok(Command::Delete(index)),
+                    _ => usage("delete <N>"),
+                },
+                _ => usage("delete <N>"),
+            },
+            ("breakpoints", []) | ("i", []) => Ok(Command::Breakpoints),
+            ("registers", []) | ("r", []) => Ok(Command::Registers),
+            ("list", []) | ("l", []) => Ok(Command::List),
+            ("help", []) | ("h", []) => Ok(Command::Help),
+            ("quit", []) | ("q", []) => Ok(Command::Quit),
+            ("continue", _) | ("c", _)    => usage("continue"),
+            ("breakpoints", _) | ("i", _) => usage("breakpoints"),
+            ("registers", _) | ("r", _)   => usage("registers"),
+            ("list", _) | ("l", _)        => usage("list"),
+            ("help", _) | ("h", _)        => usage("help"),
+            ("quit", _) | ("q", _)        => usage("quit"),
+            (command, _) => Err(CommandError::Unknown(String::from(command))),
+        }
+    }
+}
+
+/// Steps through a program, stopping at breakpoints, while keeping the source of the program for display.
+pub struct Debugger<'a> {
+    lines:       Vec<&'a str>,
+    file:        RegisterFile,
+    machine:     Machine,
+    breakpoints: Vec<Breakpoint>,
+}
+
+impl<'a> Debugger<'a> {
+    /// Parses the program in the `input` for a machine with the registers of the `file`, which will be executed
+    /// with the given initial `registers`.
+    pub fn new(input: &'a str, file: &RegisterFile, registers: &[i64]) -> Result<Debugger<'a>, ParseError<ErrorKind>> {
+        let program = parse_program_with(input, file)?;
+        Ok(Debugger {
+            lines:       input.lines().collect(),
+            file:        file.clone(),
+            machine:     Machine::with_registers(program, registers),
+            breakpoints: Vec::new(),
+        })
+    }
+
+    /// The machine that is being debugged.
+    pub fn machine(&self) -> &Machine { &self.machine }
+
+    /// The registers of the machine, which commands may refer to by name.
+    pub fn register_file(&self) -> &RegisterFile { &self.file }
+
+    /// Describes the line that will be executed next, along with the instruction that will execute it.
+    pub fn location(&self) -> String {
+        let pc = self.machine.pc();
+        match self.machine.current() {
+            Some(instruction) => {
+                let source = self.machine.source()[pc];
+                let mut notes = String::new();
+                if source != self.machine.original()[pc] { notes.push_str(&format!(" (toggled to {:?})", source)); }
+                if source != *instruction { notes.push_str(&format!(" (optimized from {:?})", source)); }
+                format!("line {}: {}  =>  {:?}{}", pc + 1, self.lines[pc].trim(), instruction, notes)
+            },
+            None => format!("halted after line {}", self.lines.len()),
+        }
+    }
+
+    /// Describes the value of every register.
+    pub fn registers(&self) -> String { self.file.describe(self.machine.registers()) }
+
+    /// The breakpoint that the machine has reached, if any.
+    fn reached(&self) -> Option<Breakpoint> {
+        let registers = self.machine.registers();
+        self.breakpoints.iter().cloned().find(|breakpoint| match *breakpoint {
+            Breakpoint::Line(line) => line == self.machine.pc() + 1,
+            Breakpoint::Condition(register, comparison, value) => comparison.holds(registers[register], value),
+        })
+    }
+
+    /// Executes the command, returning the text that describes its result.
+    pub fn execute(&mut self, command: Command) -> String {
+        match command {
+            Command::Step(count) => {
+                let mut output = Vec::new();
+                for _ in 0..count {
+                    if let Status::Output(value) = self.machine.step() { output.push(format!("out: {}", value)); }
+                    if self.machine.status() == Status::Halted { break }
+                }
+                output.push(self.location());
+                output.push(self.registers());
+                output.join("\n")
+            },
+            Command::Continue => {
+                let mut output = Vec::new();
+                loop {
+                    match self.machine.step() {
+                        Status::Halted => break,
+                        Status::Output(value) => output.push(format!("out: {}", value)),
+                        Status::Running => (),
+                    }
+                    if let Some(breakpoint) = self.reached() {
+                        output.push(format!("stopped at {}", breakpoint.describe(&self.file)));
+                        break
+                    }
+                }
+                output.push(self.location());
+                output.push(self.registers());
+                output.join("\n")
+            },
+            Command::Break(breakpoint) => {
+                if let Breakpoint::Line(line) = breakpoint {
+                    if line > self.lines.len() {
+                        return format!("there is no line {}, as the program has {} lines", line, self.lines.len());
+                    }
+                }
+                self.breakpoints.push(breakpoint);
+                format!("breakpoint {}: {}", self.breakpoints.len(), breakpoint.describe(&self.file))
+            },
+            Command::Delete(index) => {
+                if index > self.breakpoints.len() { return format!("there is no breakpoint {}", index); }
+                format!("deleted breakpoint {}: {}", index, self.breakpoints.remove(index - 1).describe(&self.file))
+            },
+            Command::Breakpoints if self.breakpoints.is_empty() => String::from("there are no breakpoints"),
+            Command::Breakpoints => self.breakpoints.iter().enumerate()
+                .map(|(index, breakpoint)| format!("{}: {}", index + 1, breakpoint.describe(&self.file)))
+                .collect::<Vec<String>>().join("\n"),
+            Command::Registers => self.registers(),
+            Command::List => self.lines.iter().zip(self.machine.program()).enumerate()
+                .map(|(index, (line, instruction))| {
+                    let marker = if index == self.machine.pc() { '>' } else { ' ' };
+                    format!("{} {:>3} | {:<16}{:?}", marker, index + 1, line.trim(), instruction)
+                }).collect::<Vec<String>>().join("\n"),
+            Command::Help => String::from(HELP),
+            Command::Quit => String::new(),
+        }
+    }
+}
+
+#[test]
+fn commands() {
+    let registers = RegisterFile::default();
+    let parse = |input| Command::parse(input, &registers);
+    assert_eq!(Ok(Command::Step(1)), parse("step"));
+    assert_eq!(Ok(Command::Step(5)), parse("s 5"));
+    assert_eq!(Ok(Command::Break(Breakpoint::Line(3))), parse("break 3"));
+    let condition = Breakpoint::Condition(2, Comparison::GreaterOrEqual, -4);
+    assert_eq!(Ok(Command::Break(condition)), parse("b c >= -4"));
+    assert_eq!(Ok(Command::Continue), parse(" c "));
+    assert_eq!(Err(CommandError::Usage("step [N]")), parse("step x"));
+    assert_eq!(Err(CommandError::Usage("delete <N>")), parse("delete 0"));
+    assert!(parse("break e == 1").is_err());
+    assert_eq!(Err(CommandError::Unknown(String::from("jump"))), parse("jump 4"));
+
+    let registers = RegisterFile::new(&["x", "y", "z", "w", "acc"]).unwrap();
+    let condition = Breakpoint::Condition(4, Comparison::Less, 0);
+    assert_eq!(Ok(Command::Break(condition)), Command::parse("b acc < 0", &registers));
+    assert_eq!("acc < 0", condition.describe(&registers));
+    assert!(Command::parse("b a < 0", &registers).is_err());
+}
+
+#[test]
+fn debugging() {
+    let input = "cpy 3 b\ninc a\ndec b\njnz b -2\ncpy a c\ndec c\njnz c -1";
+    let mut debugger = Debugger::new(input, &RegisterFile::default(), &[0; 4]).unwrap();
+    assert_eq!("line 1: cpy 3 b  =>  CopyInteger(3, 1)", debugger.location());
+    assert_eq!("line 2: inc a  =>  NoOp (optimized from Increment(0))\na=0 b=3 c=0 d=0",
+        debugger.execute(Command::Step(1)));
+
+    debugger.execute(Command::Break(Breakpoint::Condition(2, Comparison::Equal, 3)));
+    debugger.execute(Command::Break(Breakpoint::Line(6)));
+    assert_eq!("stopped at c == 3\nline 6: dec c  =>  NoOp (optimized from Decrement(2))\na=3 b=0 c=3 d=0",
+        debugger.execute(Command::Continue));
+    assert_eq!("deleted breakpoint 1: c == 3", debugger.execute(Command::Delete(1)));
+    assert_eq!("halted after line 7\na=3 b=0 c=0 d=0", debugger.execute(Command::Continue));
+    assert_eq!("1: line 6", debugger.execute(Command::Breakpoints));
+}
+
+#[test]
+fn toggled_lines() {
+    let mut debugger = Debugger::new("cpy 1 a\ntgl a\ninc b", &RegisterFile::default(), &[0; 4]).unwrap();
+    assert_eq!("line 3: inc b  =>  Decrement(1) (toggled to Decrement(1))", debugger.execute(Command::Step(2)).lines()
+        .next().unwrap());
+}
+
+#[test]
+fn named_registers() {
+    let registers = RegisterFile::new(&["x", "y", "z", "w", "acc"]).unwrap();
+    let mut debugger = Debugger::new("cpy 4 acc\ndec acc\ninc x\ninc x\njnz acc -3", &registers, &[0; 5]).unwrap();
+    debugger.execute(Command::parse("b acc == 2", debugger.register_file()).unwrap());
+    assert_eq!("stopped at acc == 2", debugger.execute(Command::Continue).lines().next().unwrap());
+    assert_eq!("x=2 y=0 z=0 w=0 acc=2", debugger.registers());
+}
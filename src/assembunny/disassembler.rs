@@ -0,0 +1,82 @@
+//! Writes programs back out as assembunny, either in the canonical form that the parser reads, so that
+//! hand-edited programs can be normalized and compared, or as a listing that numbers each line and resolves
+//! the line that each jump and toggle targets.
+use super::{Instruction, Kind, RegisterFile};
+
+/// Writes the program in canonical assembunny syntax, one instruction per line, with the names of the given
+/// `registers`, such that parsing the output with the same registers results in the same program.
+pub fn format_program(program: &[Instruction], registers: &RegisterFile) -> String {
+    program.iter().map(|instruction| format!("{}\n", instruction.named(registers))).collect()
+}
+
+/// Lists the program with the number of each line, counting from one, followed by the line that each jump or
+/// toggle with a constant offset targets, such as `   3  jnz c 2  ; -> 5`. A target outside of the program
+/// is described as leaving the program. Registers are written with the names of the given `registers`.
+pub fn disassemble(program: &[Instruction], registers: &RegisterFile) -> String {
+    let lines = program.iter().map(|instruction| instruction.named(registers).to_string()).collect::<Vec<String>>();
+    let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+    let mut output = String::new();
+    for ((pc, instruction), text) in program.iter().enumerate().zip(lines) {
+        let comment = match *instruction {
+            Instruction::JumpIf(_, Kind::Value(offset)) => format!("  ; -> {}", target(program, pc, offset)),
+            Instruction::Toggle(Kind::Value(offset))    => format!("  ; toggles {}", target(program, pc, offset)),
+            _ => String::new(),
+        };
+        let line = format!("{:>4}  {:<width$}{}", pc + 1, text, comment, width = width);
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+/// Describes the line that is `offset` lines away from the instruction at `pc`.
+fn target(program: &[Instruction], pc: usize, offset: i64) -> String {
+    match (pc as i64).checked_add(offset) {
+        Some(target) if target >= 0 && target < program.len() as i64 => (target + 1).to_string(),
+        _ => String::from("outside of the program"),
+    }
+}
+
+#[test]
+fn round_trip() {
+    use super::{parse_program, parse_program_with};
+
+    let registers = RegisterFile::default();
+    let samples = [
+        "cpy 41 a\ninc a\ninc a\ndec a\njnz a 2\ndec a\n",
+        include_str!("../12/input.txt"),
+        "cpy a b\ndec b\ncpy 2 c\ntgl c\njnz 1 c\ncpy -16 c\nout b\njnz 0 -3\ntgl -1\nout 7\n",
+    ];
+    for source in &samples {
+        let program = parse_program(source).unwrap();
+        assert_eq!(program, parse_program(&format_program(&program, &registers)).unwrap());
+    }
+
+    // Programs for other registers are written with the names of those registers.
+    let registers = RegisterFile::new(&["x", "y"]).unwrap();
+    let program = parse_program_with("cpy 1 x\ninc y\njnz y -1", &registers).unwrap();
+    assert_eq!("cpy 1 x\ninc y\njnz y -1\n", format_program(&program, &registers));
+    assert_eq!(program, parse_program_with(&format_program(&program, &registers), &registers).unwrap());
+    assert_eq!("   1  cpy 1 x\n   2  inc y\n   3  jnz y -1  ; -> 2\n", disassemble(&program, &registers));
+
+    // Extra whitespace is normalized away.
+    let program = parse_program("  cpy   41  a\ninc\ta ").unwrap();
+    assert_eq!("cpy 41 a\ninc a\n", format_program(&program, &RegisterFile::default()));
+}
+
+#[test]
+fn listing() {
+    let program = super::parse_program("cpy 2 c\ninc a\ndec c\njnz c -2\ntgl c\njnz 1 -9\njnz a b").unwrap();
+    let expected = "   1  cpy 2 c
+   2  inc a
+   3  dec c
+   4  jnz c -2  ; -> 2
+   5  tgl c
+   6  jnz 1 -9  ; -> outside of the program
+   7  jnz a b
+";
+    let registers = RegisterFile::default();
+    assert_eq!(expected, disassemble(&program, &registers));
+    let program = super::parse_program("tgl 1\ninc a").unwrap();
+    assert_eq!("   1  tgl 1  ; toggles 2\n   2  inc a\n", disassemble(&program, &registers));
+}
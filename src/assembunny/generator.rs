@@ -0,0 +1,344 @@
+//! Generates random assembunny programs that are well-formed, so that the optimizer and the compiler can be
+//! checked against a naive reference interpreter, and shrinks any program that exposes a difference between
+//! them down to a minimal reproducer.
+//!
+//! Programs are generated as a tree of statements, which keeps every jump valid as the program is shrunk.
+//! Each loop counts down from a positive constant, and no instruction within a loop may write to the counter
+//! of that loop, so every loop ends, and every other jump leads forward. A loop may be entered partway through
+//! its body, and instructions may be toggled, as both are shapes that the optimizer must not break. A toggle
+//! may target itself, an earlier line, or a line that is only known at runtime, and so may turn any line into
+//! a jump that never ends, which is why the reference interpreter is only run for a bounded number of steps.
+use super::{Instruction, Kind, Program, RunOutcome};
+
+/// The number of registers that generated programs use, which are the default registers `a` to `d`.
+const REGISTERS: usize = 4;
+
+/// How deeply loops and skips may be nested within each other.
+const DEPTH: usize = 3;
+
+/// A part of a generated program.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Statement {
+    /// A `cpy`, `inc`, `dec`, `out`, or `tgl` instruction.
+    Instruction(Instruction),
+    /// `jnz x N` followed by the statements that it skips when `x` is not zero.
+    Skip(Kind, Vec<Statement>),
+    /// `cpy N r`, followed by the statements of the loop, and then by `dec r; jnz r -M` to repeat them `N`
+    /// times, where `N` is positive.
+    Loop(usize, i64, Vec<Statement>),
+    /// A loop that begins as `cpy N r; jnz x M`, which skips over the first of the statements of the loop,
+    /// and into the second, during the first iteration when `x` is not zero.
+    Entered(Kind, usize, i64, Vec<Statement>, Vec<Statement>),
+    /// `tgl 1`, followed by the `inc`, `dec`, or `out` instruction that it toggles.
+    Toggled(Instruction),
+}
+
+/// A small pseudorandom generator of programs, which always generates the same programs from the same seed.
+#[derive(Clone, Debug)]
+pub struct Generator {
+    state: u64,
+}
+
+impl Generator {
+    pub fn new(seed: u64) -> Generator {
+        // The seed is scrambled, as xorshift can not start from zero, and starts poorly from small seeds.
+        let mut state = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        state = (state ^ (state >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        state = (state ^ (state >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        Generator { state: (state ^ (state >> 31)) | 1 }
+    }
+
+    /// A pseudorandom number from `0` up to, but not including, `bound`, using xorshift64*.
+    fn below(&mut self, bound: u64) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D) % bound
+    }
+
+    fn register(&mut self) -> usize { self.below(REGISTERS as u64) as usize }
+
+    fn operand(&mut self) -> Kind {
+        if self.below(2) == 0 { Kind::Register(self.register()) } else { Kind::Value(self.below(11) as i64 - 5) }
+    }
+
+    /// Generates the statements of a program with up to `size` statements at its top level.
+    pub fn statements(&mut self, size: usize) -> Vec<Statement> {
+        let size = 1 + self.below(size.max(1) as u64) as usize;
+        self.block(size, 0, &[])
+    }
+
+    /// Generates `size` statements, nested `depth` deep within loops and skips, that may not write to any of
+    /// the `counters` of the loops that they are nested within.
+    fn block(&mut self, size: usize, depth: usize, counters: &[usize]) -> Vec<Statement> {
+        let writable = (0..REGISTERS).filter(|register| !counters.contains(register)).collect::<Vec<usize>>();
+        (0..size).map(|_| match self.below(10) {
+            0 | 1 if depth < DEPTH => {
+                let counter = writable[self.below(writable.len() as u64) as usize];
+                let mut nested = counters.to_vec();
+                nested.push(counter);
+                let (count, size) = (1 + self.below(5) as i64, self.below(3) as usize);
+                Statement::Loop(counter, count, self.block(size, depth + 1, &nested))
+            },
+            2 if depth < DEPTH => {
+                let condition = if self.below(3) == 0 { Kind::Value(self.below(2) as i64) } else { self.operand() };
+                let size = 1 + self.below(3) as usize;
+                Statement::Skip(condition, self.block(size, depth + 1, counters))
+            },
+            3 if depth < DEPTH => {
+                let counter = writable[self.below(writable.len() as u64) as usize];
+                let mut nested = counters.to_vec();
+                nested.push(counter);
+                let condition = if self.below(2) == 0 { Kind::Value(1) } else { self.operand() };
+                let (count, head, tail) = (1 + self.below(5) as i64, self.below(3) as usize, self.below(3) as usize);
+                let (head, tail) = (self.block(head, depth + 1, &nested), self.block(tail, depth + 1, &nested));
+                Statement::Entered(condition, counter, count, head, tail)
+            },
+            4 if self.below(2) == 0 => {
+                let target = writable[self.below(writable.len() as u64) as usize];
+                Statement::Toggled(match self.below(3) {
+                    0 => Instruction::Increment(target),
+                    1 => Instruction::Decrement(target),
+                    _ if self.below(2) == 0 => Instruction::Out(Kind::Register(target)),
+                    _ => Instruction::Out(Kind::Value(self.below(11) as i64 - 5)),
+                })
+            },
+            4 => Statement::Instruction(Instruction::Toggle(match self.below(3) {
+                0 => Kind::Value(0),
+                1 => Kind::Value(self.below(9) as i64 - 6),
+                _ => Kind::Register(self.register()),
+            })),
+            _ => {
+                let target = writable[self.below(writable.len() as u64) as usize];
+                Statement::Instruction(match self.below(6) {
+                    0 => Instruction::CopyInteger(self.below(11) as i64 - 5, target),
+                    1 => Instruction::CopyRegister(self.register(), target),
+                    2 | 3 => Instruction::Increment(target),
+                    4 => Instruction::Decrement(target),
+                    _ => Instruction::Out(self.operand()),
+                })
+            },
+        }).collect()
+    }
+}
+
+/// Writes the statements out as the instructions of a program.
+pub fn render(statements: &[Statement]) -> Program {
+    let mut program = Program::new();
+    for statement in statements {
+        match *statement {
+            Statement::Instruction(instruction) => program.push(instruction),
+            Statement::Skip(condition, ref skipped) => {
+                let skipped = render(skipped);
+                program.push(Instruction::JumpIf(condition, Kind::Value(skipped.len() as i64 + 1)));
+                program.extend(skipped);
+            },
+            Statement::Loop(counter, count, ref body) => {
+                let body = render(body);
+                let offset = body.len() as i64 + 1;
+                program.push(Instruction::CopyInteger(count, counter));
+                program.extend(body);
+                program.push(Instruction::Decrement(counter));
+                program.push(Instruction::JumpIf(Kind::Register(counter), Kind::Value(-offset)));
+            },
+            Statement::Entered(condition, counter, count, ref head, ref tail) => {
+                let (head, tail) = (render(head), render(tail));
+                let offset = (head.len() + tail.len()) as i64 + 1;
+                program.push(Instruction::CopyInteger(count, counter));
+                program.push(Instruction::JumpIf(condition, Kind::Value(head.len() as i64 + 1)));
+                program.extend(head);
+                program.extend(tail);
+                program.push(Instruction::Decrement(counter));
+                program.push(Instruction::JumpIf(Kind::Register(counter), Kind::Value(-offset)));
+            },
+            Statement::Toggled(instruction) => {
+                program.push(Instruction::Toggle(Kind::Value(1)));
+                program.push(instruction);
+            },
+        }
+    }
+    program
+}
+
+/// Every program that is one step simpler than the given statements: with a statement removed, with a loop
+/// or skip replaced by the statements within it, with a loop that repeats only once, with a loop that is no
+/// longer entered partway through, with an instruction that is no longer toggled, or with a copied constant
+/// set to zero.
+fn simplifications(statements: &[Statement]) -> Vec<Vec<Statement>> {
+    let mut candidates = Vec::new();
+    for (index, statement) in statements.iter().enumerate() {
+        let replace = |replacement: &[Statement]| {
+            let mut candidate = statements[..index].to_vec();
+            candidate.extend_from_slice(replacement);
+            candidate.extend_from_slice(&statements[index + 1..]);
+            candidate
+        };
+
+        candidates.push(replace(&[]));
+        match *statement {
+            Statement::Instruction(Instruction::CopyInteger(value, register)) if value != 0 => {
+                candidates.push(replace(&[Statement::Instruction(Instruction::CopyInteger(0, register))]));
+            },
+            Statement::Instruction(_) => (),
+            Statement::Skip(condition, ref skipped) => {
+                candidates.push(replace(skipped));
+                for simpler in simplifications(skipped) {
+                    candidates.push(replace(&[Statement::Skip(condition, simpler)]));
+                }
+            },
+            Statement::Loop(counter, count, ref body) => {
+                candidates.push(replace(body));
+                if count > 1 { candidates.push(replace(&[Statement::Loop(counter, 1, body.clone())])); }
+                for simpler in simplifications(body) {
+                    candidates.push(replace(&[Statement::Loop(counter, count, simpler)]));
+                }
+            },
+            Statement::Entered(condition, counter, count, ref head, ref tail) => {
+                let body = head.iter().chain(tail).cloned().collect::<Vec<Statement>>();
+                candidates.push(replace(&[Statement::Loop(counter, count, body)]));
+                if count > 1 {
+                    candidates.push(replace(&[Statement::Entered(condition, counter, 1, head.clone(), tail.clone())]));
+                }
+                for simpler in simplifications(head) {
+                    candidates.push(replace(&[Statement::Entered(condition, counter, count, simpler, tail.clone())]));
+                }
+                for simpler in simplifications(tail) {
+                    candidates.push(replace(&[Statement::Entered(condition, counter, count, head.clone(), simpler)]));
+                }
+            },
+            Statement::Toggled(instruction) => candidates.push(replace(&[Statement::Instruction(instruction)])),
+        }
+    }
+    candidates
+}
+
+/// Repeatedly simplifies the statements for as long as the simpler program still `fails`, returning the
+/// simplest program that was found to fail.
+pub fn shrink<F: Fn(&[Statement]) -> bool>(statements: Vec<Statement>, fails: F) -> Vec<Statement> {
+    let mut statements = statements;
+    while let Some(simpler) = simplifications(&statements).into_iter().find(|candidate| fails(candidate)) {
+        statements = simpler;
+    }
+    statements
+}
+
+/// Executes the unoptimized `program` one instruction at a time, in the most direct way possible, so that
+/// it may serve as the reference that the machine is compared against. No more than `budget` instructions are
+/// executed, as a program that toggles its own jumps may never halt.
+pub fn reference(program: &[Instruction], registers: &[i64], budget: u64) -> (RunOutcome, Vec<i64>) {
+    let mut program = program.to_vec();
+    let mut registers = registers.to_vec();
+    let (mut pc, mut steps) = (0i64, 0);
+    while pc >= 0 && (pc as usize) < program.len() {
+        if steps == budget { return (RunOutcome::BudgetExhausted, registers) }
+        steps += 1;
+
+        let value = |registers: &[i64], kind| match kind { Kind::Register(x) => registers[x], Kind::Value(x) => x };
+        match program[pc as usize] {
+            Instruction::CopyInteger(value, register) => registers[register] = value,
+            Instruction::CopyRegister(x, y) => registers[y] = registers[x],
+            Instruction::Increment(register) => registers[register] = registers[register].wrapping_add(1),
+            Instruction::Decrement(register) => registers[register] = registers[register].wrapping_sub(1),
+            Instruction::JumpIf(condition, offset) if value(&registers, condition) != 0 => {
+                pc += value(&registers, offset);
+                continue
+            },
+            Instruction::Toggle(offset) => {
+                let target = pc + value(&registers, offset);
+                if target >= 0 && (target as usize) < program.len() {
+                    program[target as usize] = program[target as usize].toggled();
+                }
+            },
+            _ => (),
+        }
+        pc += 1;
+    }
+    (if pc < 0 { RunOutcome::JumpOutOfRange } else { RunOutcome::Halted }, registers)
+}
+
+#[test]
+fn shrinking() {
+    // Only a loop that increments `b` can produce the failure, which is kept, while everything else is removed.
+    let statements = vec![
+        Statement::Instruction(Instruction::CopyInteger(3, 0)),
+        Statement::Skip(Kind::Register(0), vec![Statement::Instruction(Instruction::Increment(2))]),
+        Statement::Loop(3, 4, vec![
+            Statement::Instruction(Instruction::Decrement(0)),
+            Statement::Instruction(Instruction::Increment(1)),
+        ]),
+    ];
+    let fails = |statements: &[Statement]| reference(&render(statements), &[0; REGISTERS], 100).1[1] >= 2;
+    let expected = vec![Statement::Loop(3, 2, vec![Statement::Instruction(Instruction::Increment(1))])];
+    assert_eq!((RunOutcome::Halted, vec![0, 2, 0, 0]), reference(&render(&expected), &[0; REGISTERS], 100));
+    assert!(fails(&statements));
+
+    let minimal = shrink(statements, fails);
+    assert_eq!(1, minimal.len());
+    assert!(fails(&minimal));
+    let listing = super::format_program(&render(&expected), &super::RegisterFile::default());
+    assert_eq!("cpy 2 d\ninc b\ndec d\njnz d -2\n", listing);
+}
+
+#[test]
+fn shapes() {
+    // The loop is entered at its `tgl 1` on the first iteration, and each `tgl 1` flips `dec a` and `inc a`.
+    let head = vec![Statement::Instruction(Instruction::Increment(0))];
+    let tail = vec![Statement::Toggled(Instruction::Decrement(0))];
+    let statements = vec![Statement::Entered(Kind::Value(1), 1, 3, head, tail)];
+    let program = render(&statements);
+    let listing = super::format_program(&program, &super::RegisterFile::default());
+    assert_eq!("cpy 3 b\njnz 1 2\ninc a\ntgl 1\ndec a\ndec b\njnz b -4\n", listing);
+    assert_eq!((RunOutcome::Halted, vec![3, 0, 0, 0]), reference(&program, &[0; REGISTERS], 100));
+    assert_eq!(&reference(&program, &[0; REGISTERS], 100).1[..], super::Machine::new(program).run());
+
+    // Toggling itself turns `tgl 0` into an `inc` of a constant, which is skipped, while toggling backwards
+    // turns `cpy 1 c` into a jump that repeats forever, and toggling `cpy -3 b` leaves the program.
+    let program = super::parse_program("tgl 0\ntgl 0\ninc b").unwrap();
+    assert_eq!((RunOutcome::Halted, vec![0, 1, 0, 0]), reference(&program, &[0; REGISTERS], 100));
+    let program = super::parse_program("cpy -2 a\ninc b\ntgl a\ncpy 1 c\ndec b\njnz 1 -2").unwrap();
+    assert_eq!(RunOutcome::BudgetExhausted, reference(&program, &[0; REGISTERS], 100).0);
+    let program = super::parse_program("cpy -3 b\ntgl -1\njnz 1 -2").unwrap();
+    assert_eq!((RunOutcome::JumpOutOfRange, vec![0, -3, 0, 0]), reference(&program, &[0; REGISTERS], 100));
+}
+
+#[test]
+fn differential() {
+    use super::{format_program, Compiled, Machine, RegisterFile};
+    use day12::{calculate, Answer};
+
+    // Runs the program through the reference interpreter, and then through every way that the machine can
+    // run it, describing the first that disagrees with the reference. A program that the reference could not
+    // finish within its budget can not be compared, as the machine may have optimized it into fewer steps.
+    const BUDGET: u64 = 10_000;
+    let file = RegisterFile::default();
+    let mismatch = |statements: &[Statement], registers: &[i64]| -> Option<String> {
+        let program = render(statements);
+        let expected = reference(&program, registers, BUDGET);
+        if expected.0 == RunOutcome::BudgetExhausted { return None }
+
+        let mut machine = Machine::with_registers(program.clone(), registers);
+        let optimized = (machine.run_bounded(Some(BUDGET), None), machine.registers().to_vec());
+        let compiled = Compiled::new(program.clone()).run(registers, Some(BUDGET));
+        let calculated = calculate(registers, &format_program(&program, &file), Some(BUDGET), None).unwrap();
+        if optimized != expected {
+            Some(format!("the machine left {:?}, rather than {:?}", optimized, expected))
+        } else if compiled != expected {
+            Some(format!("the compiled program left {:?}, rather than {:?}", compiled, expected))
+        } else if calculated != Answer(expected.0, expected.1[0]) {
+            Some(format!("calculate returned {:?}, rather than {:?}", calculated, expected))
+        } else {
+            None
+        }
+    };
+
+    for seed in 0..500 {
+        let mut generator = Generator::new(seed);
+        let statements = generator.statements(12);
+        let registers = (0..REGISTERS).map(|_| generator.below(7) as i64 - 3).collect::<Vec<i64>>();
+        if mismatch(&statements, &registers).is_some() {
+            let minimal = shrink(statements, |statements| mismatch(statements, &registers).is_some());
+            panic!("seed {} with registers {:?}: {}\n{}", seed, registers,
+                mismatch(&minimal, &registers).unwrap(), format_program(&render(&minimal), &file));
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use super::{Program, RegisterFile, DEFAULT_NAMES};
+use parse::{numbered_lines, ParseError};
+use std::fmt::{self, Display};
+use std::iter::Enumerate;
+use std::str::Lines;
+
+/// Parses each line of an assembunny program into an `Instruction`.
+pub struct InstructionIter<'a> {
+    instructions: Enumerate<Lines<'a>>,
+    registers:    &'a RegisterFile,
+}
+
+impl<'a> InstructionIter<'a> {
+    pub fn new(input: &'a str, registers: &'a RegisterFile) -> InstructionIter<'a> {
+        InstructionIter { instructions: input.lines().enumerate(), registers: registers }
+    }
+}
+
+/// A single assembunny instruction, where registers are referred to by their index.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Instruction {
+    /// Optimized instruction to add x to y and set x to 0.
+    Add(usize, usize),
+    /// Optimized instruction to add the product of x and y to the last register, setting y and the scratch
+    /// register that precedes the last to 0.
+    Multiply(Kind, usize, usize, usize),
+    /// Copy value `i64` into register `usize`
+    CopyInteger(i64, usize),
+    /// Copy from left register into right register
+    CopyRegister(usize, usize),
+    /// Increment register
+    Increment(usize),
+    /// Decrement register
+    Decrement(usize),
+    /// Skip by the right register or value if the left register or value is not zero
+    JumpIf(Kind, Kind),
+    /// Toggle the instruction that is offset from this one by the register or value
+    Toggle(Kind),
+    /// Emit the register or value as the next value of the output
+    Out(Kind),
+    /// A toggled instruction whose operands are invalid for it, which is skipped
+    Skip(Invalid),
+    /// Do nothing, successfully
+    NoOp,
+}
+
+/// An operand which may either be read from a register or given as a value.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Kind {
+    Register(usize),
+    Value(i64),
+}
+
+/// The forms that an instruction may be toggled into which cannot be executed, because they would write
+/// to a value rather than to a register. The operands are kept so that the instruction can be toggled back.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Invalid {
+    Increment(i64),
+    Decrement(i64),
+    Copy(Kind, i64),
+}
+
+impl Instruction {
+    /// The instruction that this one becomes when it is toggled. One-argument instructions become `dec` if
+    /// they were `inc`, and `inc` otherwise, whereas two-argument instructions become `cpy` if they were
+    /// `jnz`, and `jnz` otherwise. The optimized `Add`, `Multiply`, and `NoOp` instructions have no assembunny
+    /// form of their own, and so are returned unchanged.
+    pub fn toggled(self) -> Instruction {
+        match self {
+            Instruction::Increment(register)          => Instruction::Decrement(register),
+            Instruction::Decrement(register)          => Instruction::Increment(register),
+            Instruction::Toggle(Kind::Register(x))    => Instruction::Increment(x),
+            Instruction::Toggle(Kind::Value(x))       => Instruction::Skip(Invalid::Increment(x)),
+            Instruction::Out(Kind::Register(x))       => Instruction::Increment(x),
+            Instruction::Out(Kind::Value(x))          => Instruction::Skip(Invalid::Increment(x)),
+            Instruction::Skip(Invalid::Increment(x))  => Instruction::Skip(Invalid::Decrement(x)),
+            Instruction::Skip(Invalid::Decrement(x))  => Instruction::Skip(Invalid::Increment(x)),
+            Instruction::CopyInteger(x, y)            => Instruction::JumpIf(Kind::Value(x), Kind::Register(y)),
+            Instruction::CopyRegister(x, y)           => Instruction::JumpIf(Kind::Register(x), Kind::Register(y)),
+            Instruction::Skip(Invalid::Copy(x, y))    => Instruction::JumpIf(x, Kind::Value(y)),
+            Instruction::JumpIf(x, Kind::Value(y))    => Instruction::Skip(Invalid::Copy(x, y)),
+            Instruction::JumpIf(Kind::Value(x), Kind::Register(y))    => Instruction::CopyInteger(x, y),
+            Instruction::JumpIf(Kind::Register(x), Kind::Register(y)) => Instruction::CopyRegister(x, y),
+            Instruction::Add(..) | Instruction::Multiply(..) | Instruction::NoOp => self,
+        }
+    }
+}
+
+/// An instruction that is written in canonical assembunny syntax, using the names of a register file.
+pub struct Named<'a> {
+    instruction: Instruction,
+    registers:   &'a RegisterFile,
+}
+
+impl Instruction {
+    /// Writes the instruction with the names of the given `registers`, rather than the default names.
+    pub fn named(self, registers: &RegisterFile) -> Named<'_> { Named { instruction: self, registers: registers } }
+
+    fn write<'a>(&self, f: &mut fmt::Formatter, name: &dyn Fn(usize) -> &'a str) -> fmt::Result {
+        let kind = |kind| match kind {
+            Kind::Register(register) => String::from(name(register)),
+            Kind::Value(value)       => value.to_string(),
+        };
+
+        match *self {
+            Instruction::Add(x, y)                      => write!(f, "add {} {}", name(x), name(y)),
+            Instruction::Multiply(x, y, scratch, total) => {
+                write!(f, "mul {} {} {} {}", kind(x), name(y), name(scratch), name(total))
+            },
+            Instruction::CopyInteger(value, register)   => write!(f, "cpy {} {}", value, name(register)),
+            Instruction::CopyRegister(x, y)             => write!(f, "cpy {} {}", name(x), name(y)),
+            Instruction::Increment(register)            => write!(f, "inc {}", name(register)),
+            Instruction::Decrement(register)            => write!(f, "dec {}", name(register)),
+            Instruction::JumpIf(condition, offset)      => write!(f, "jnz {} {}", kind(condition), kind(offset)),
+            Instruction::Toggle(offset)                 => write!(f, "tgl {}", kind(offset)),
+            Instruction::Out(value)                     => write!(f, "out {}", kind(value)),
+            Instruction::Skip(Invalid::Increment(x))    => write!(f, "inc {}", x),
+            Instruction::Skip(Invalid::Decrement(x))    => write!(f, "dec {}", x),
+            Instruction::Skip(Invalid::Copy(x, y))      => write!(f, "cpy {} {}", kind(x), y),
+            Instruction::NoOp                           => f.write_str("nop"),
+        }
+    }
+}
+
+/// Writes the instruction in canonical assembunny syntax with the default register names, which parses back
+/// into the same instruction. The instructions that only exist after a toggle are written as the invalid
+/// assembunny that they came from, such as `inc 2`, while those that only exist after optimization are written
+/// as the pseudo-instructions `add`, `mul`, and `nop`. Neither of these can be parsed.
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, &|register| DEFAULT_NAMES.get(register).cloned().unwrap_or("?"))
+    }
+}
+
+impl<'a> Display for Named<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.instruction.write(f, &|register| self.registers.name(register))
+    }
+}
+
+/// Why an assembunny instruction could not be parsed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// The line does not begin with `cpy`, `inc`, `dec`, `jnz`, `tgl`, or `out`.
+    UnknownInstruction,
+    /// The instruction is missing one of its operands.
+    MissingOperand,
+    /// An operand that must be a register is not the name of one of the machine's registers.
+    InvalidRegister,
+    /// An operand that may be either a register or an integer is neither.
+    InvalidOperand,
+    /// The offset of a jump is neither a register nor an integer.
+    InvalidOffset,
+    /// Text follows the last operand of the instruction.
+    TrailingText,
+    /// The program has more instructions than the given limit allows.
+    TooManyInstructions(usize),
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ErrorKind::UnknownInstruction  => {
+                f.write_str("expected one of 'cpy', 'inc', 'dec', 'jnz', 'tgl', or 'out'")
+            },
+            ErrorKind::MissingOperand      => f.write_str("expected another operand"),
+            ErrorKind::InvalidRegister     => f.write_str("expected the name of a register"),
+            ErrorKind::InvalidOperand      => f.write_str("expected either a register or an integer"),
+            ErrorKind::InvalidOffset       => {
+                f.write_str("expected the jump offset to be either a register or an integer")
+            },
+            ErrorKind::TrailingText        => f.write_str("expected the instruction to end"),
+            ErrorKind::TooManyInstructions(limit) => write!(f, "expected no more than {} instructions", limit),
+        }
+    }
+}
+
+/// Parses an operand that may either be a register or an integer.
+fn operand_kind(input: &str, registers: &RegisterFile) -> Option<Kind> {
+    match (input.parse::<i64>(), registers.index(input)) {
+        (Ok(value), _)      => Some(Kind::Value(value)),
+        (_, Some(register)) => Some(Kind::Register(register)),
+        _                   => None,
+    }
+}
+
+/// Parses a single line of an assembunny program, found at line `number`, which may only refer to the given
+/// `registers`.
+pub fn parse_instruction(number: usize, line: &str, registers: &RegisterFile)
+    -> Result<Instruction, ParseError<ErrorKind>>
+{
+    let mut elements = line.split_whitespace();
+    let error = |kind, text| Err(ParseError::new(kind, number, line, text));
+    let mut operand = || elements.next().ok_or_else(|| ParseError::new(ErrorKind::MissingOperand, number, line,
+        &line[line.len()..]));
+
+    let instruction = match operand() {
+        Ok("cpy") => {
+            let (source, target) = (operand()?, operand()?);
+            let target = match registers.index(target) {
+                Some(target) => target,
+                None => return error(ErrorKind::InvalidRegister, target),
+            };
+            match (source.parse::<i64>(), registers.index(source)) {
+                (Ok(value), _)    => Instruction::CopyInteger(value, target),
+                (_, Some(source)) => Instruction::CopyRegister(source, target),
+                _ => return error(ErrorKind::InvalidOperand, source),
+            }
+        },
+        Ok(name @ "inc") | Ok(name @ "dec") => {
+            let register = operand()?;
+            match registers.index(register) {
+                Some(register) if name == "inc" => Instruction::Increment(register),
+                Some(register) => Instruction::Decrement(register),
+                None => return error(ErrorKind::InvalidRegister, register),
+            }
+        },
+        Ok("jnz") => {
+            let (condition, offset) = (operand()?, operand()?);
+            match (operand_kind(condition, registers), operand_kind(offset, registers)) {
+                (Some(condition), Some(offset)) => Instruction::JumpIf(condition, offset),
+                (None, _) => return error(ErrorKind::InvalidOperand, condition),
+                (_, None) => return error(ErrorKind::InvalidOffset, offset),
+            }
+        },
+        Ok("tgl") => {
+            let offset = operand()?;
+            match operand_kind(offset, registers) {
+                Some(offset) => Instruction::Toggle(offset),
+                None         => return error(ErrorKind::InvalidOffset, offset),
+            }
+        },
+        Ok("out") => {
+            let value = operand()?;
+            match operand_kind(value, registers) {
+                Some(value) => Instruction::Out(value),
+                None        => return error(ErrorKind::InvalidOperand, value),
+            }
+        },
+        Ok(name) => return error(ErrorKind::UnknownInstruction, name),
+        Err(_)   => return error(ErrorKind::UnknownInstruction, &line[line.len()..]),
+    };
+
+    match elements.next() {
+        Some(extra) => error(ErrorKind::TrailingText, extra),
+        None        => Ok(instruction),
+    }
+}
+
+impl<'a> Iterator for InstructionIter<'a> {
+    type Item = Result<Instruction, ParseError<ErrorKind>>;
+    fn next(&mut self) -> Option<Result<Instruction, ParseError<ErrorKind>>> {
+        self.instructions.next().map(|(id, line)| parse_instruction(id + 1, line, self.registers))
+    }
+}
+
+/// Parses every line of the `input` as an instruction of a program for a machine with the default registers.
+pub fn parse_program(input: &str) -> Result<Program, ParseError<ErrorKind>> {
+    parse_program_with(input, &RegisterFile::default())
+}
+
+/// Parses every line of the `input` as an instruction of a program for a machine with the given `registers`.
+pub fn parse_program_with(input: &str, registers: &RegisterFile) -> Result<Program, ParseError<ErrorKind>> {
+    InstructionIter::new(input, registers).collect()
+}
+
+/// Parses every line of the `input` as an instruction of a program for a machine with the given `registers`,
+/// failing at the first instruction beyond the `limit`.
+pub fn parse_program_with_limit(input: &str, registers: &RegisterFile, limit: usize)
+    -> Result<Program, ParseError<ErrorKind>>
+{
+    let mut program = Program::new();
+    for (number, line) in numbered_lines(input) {
+        if program.len() == limit {
+            return Err(ParseError::new(ErrorKind::TooManyInstructions(limit), number, line, line));
+        }
+        program.push(parse_instruction(number, line, registers)?);
+    }
+    Ok(program)
+}
+
+#[test]
+fn parse_errors() {
+    let error = parse_program("cpy 41 a\ninc e").unwrap_err();
+    assert_eq!((ErrorKind::InvalidRegister, 2, 5, "e"), (error.kind, error.line, error.column, error.text.as_str()));
+    let parse_instruction = |number, line| parse_instruction(number, line, &RegisterFile::default());
+    assert_eq!(ErrorKind::InvalidRegister, parse_instruction(1, "cpy a 1").unwrap_err().kind);
+    assert_eq!(ErrorKind::InvalidOperand, parse_instruction(1, "cpy x a").unwrap_err().kind);
+    assert_eq!(ErrorKind::InvalidOffset, parse_instruction(1, "jnz a x").unwrap_err().kind);
+    assert_eq!(ErrorKind::InvalidOffset, parse_instruction(1, "tgl x").unwrap_err().kind);
+    assert_eq!(ErrorKind::InvalidOperand, parse_instruction(1, "out x").unwrap_err().kind);
+    assert_eq!(ErrorKind::MissingOperand, parse_instruction(1, "jnz a").unwrap_err().kind);
+    assert_eq!(ErrorKind::TrailingText, parse_instruction(1, "inc a b").unwrap_err().kind);
+    assert_eq!(ErrorKind::UnknownInstruction, parse_instruction(1, "mul a b").unwrap_err().kind);
+    assert_eq!(ErrorKind::UnknownInstruction, parse_instruction(1, "   ").unwrap_err().kind);
+
+    let program = "inc a\n".repeat(1000);
+    assert_eq!(1000, parse_program(&program).unwrap().len());
+    let registers = RegisterFile::default();
+    assert_eq!(1000, parse_program_with_limit(&program, &registers, 1000).unwrap().len());
+    let error = parse_program_with_limit(&program, &registers, 32).unwrap_err();
+    assert_eq!((ErrorKind::TooManyInstructions(32), 33), (error.kind, error.line));
+
+    let registers = RegisterFile::new(&["x", "y"]).unwrap();
+    assert_eq!(Ok(vec![Instruction::Increment(1)]), parse_program_with_limit("inc y", &registers, 1));
+    assert_eq!(ErrorKind::InvalidRegister, parse_program_with_limit("inc a", &registers, 1).unwrap_err().kind);
+}
+
+#[test]
+fn toggling() {
+    let registers = RegisterFile::default();
+    let parse_instruction = |number, line| parse_instruction(number, line, &registers);
+    let toggled = |line| parse_instruction(1, line).unwrap().toggled();
+    assert_eq!(Instruction::Decrement(0), toggled("inc a"));
+    assert_eq!(Instruction::Increment(0), toggled("dec a"));
+    assert_eq!(Instruction::Increment(1), toggled("tgl b"));
+    assert_eq!(Instruction::Increment(1), toggled("out b"));
+    assert_eq!(Instruction::Skip(Invalid::Increment(2)), toggled("tgl 2"));
+    assert_eq!(Instruction::JumpIf(Kind::Value(1), Kind::Register(2)), toggled("cpy 1 c"));
+    assert_eq!(Instruction::CopyRegister(0, 3), toggled("jnz a d"));
+    assert_eq!(Instruction::Skip(Invalid::Copy(Kind::Register(0), -2)), toggled("jnz a -2"));
+    assert_eq!(parse_instruction(1, "jnz a -2").unwrap(), toggled("jnz a -2").toggled());
+}
+
+#[test]
+fn register_files() {
+    let registers = RegisterFile::new(&["x", "y", "acc"]).unwrap();
+    let program = parse_program_with("cpy 3 x\ninc acc\ndec x\njnz x -2", &registers).unwrap();
+    assert_eq!(Instruction::Increment(2), program[1]);
+    assert_eq!("inc acc", program[1].named(&registers).to_string());
+
+    let error = parse_program_with("cpy 3 x\ninc a", &registers).unwrap_err();
+    assert_eq!((ErrorKind::InvalidRegister, 2, 5), (error.kind, error.line, error.column));
+    assert_eq!(ErrorKind::InvalidRegister, parse_program("inc acc").unwrap_err().kind);
+}
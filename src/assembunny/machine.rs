@@ -0,0 +1,458 @@
+use super::{optimize, optimize_at, Instruction, Kind, Program, RegisterFile};
+use super::tracer::Tracer;
+use std::collections::HashSet;
+use std::fmt::{self, Display};
+
+/// Whether the machine may continue executing instructions.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Status {
+    /// The program counter points to an instruction that has yet to be executed.
+    Running,
+    /// An `out` instruction emitted the value. The machine may continue, unless that was the last instruction.
+    Output(i64),
+    /// The program counter has left the program, so there is nothing more to execute.
+    Halted,
+}
+
+/// How a bounded run of a machine came to an end.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RunOutcome {
+    /// The program counter moved past the last instruction.
+    Halted,
+    /// The machine executed as many steps as it was allowed to without halting.
+    BudgetExhausted,
+    /// The machine returned to a state that it was already in, and so would run forever.
+    LoopDetected,
+    /// A jump targeted a line before the first instruction.
+    JumpOutOfRange,
+}
+
+impl Display for RunOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            RunOutcome::Halted          => "halted",
+            RunOutcome::BudgetExhausted => "step budget exhausted",
+            RunOutcome::LoopDetected    => "infinite loop detected",
+            RunOutcome::JumpOutOfRange  => "jumped before the first instruction",
+        })
+    }
+}
+
+/// Executes an assembunny program against its own registers, one instruction at a time.
+///
+/// Unless it was created with `Machine::unoptimized`, the machine executes an optimized copy of the program.
+/// As `tgl` may modify the program while it runs, toggles are applied to the source of the program, from which
+/// the optimized copy is then rebuilt, so that an optimization is never applied to instructions that no longer
+/// match it.
+#[derive(Clone, Debug)]
+pub struct Machine {
+    /// The program as it was given, which is restored when the machine is reset.
+    original:  Program,
+    /// The program with any toggles applied.
+    source:    Program,
+    /// The optimized form of the source, which is what is executed.
+    program:   Program,
+    optimized: bool,
+    registers: Vec<i64>,
+    pc:        usize,
+    /// Whether the machine halted by jumping before its first instruction.
+    escaped:   bool,
+}
+
+/// Everything that determines how a machine will continue to execute: the program counter, the registers,
+/// and the program, which may have been modified by toggles.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Snapshot {
+    pub pc:        usize,
+    pub registers: Vec<i64>,
+    pub source:    Program,
+}
+
+/// Offsets the program counter, returning `None` if the offset leads before the first instruction.
+pub(super) fn jump(pc: usize, offset: i64) -> Option<usize> {
+    if offset < 0 {
+        pc.checked_sub(offset.unsigned_abs() as usize)
+    } else {
+        Some(pc.saturating_add(offset as usize))
+    }
+}
+
+impl Machine {
+    /// Creates a machine that will execute the `program` from its first instruction, with each of the default
+    /// registers set to zero.
+    pub fn new(program: Program) -> Machine {
+        Machine::with_registers(program, &RegisterFile::default().zeroed())
+    }
+
+    /// Creates a machine that will execute the `program` from its first instruction, with the given
+    /// initial `registers`, which must include every register that the program refers to.
+    pub fn with_registers(program: Program, registers: &[i64]) -> Machine {
+        let optimized = optimize(&program);
+        Machine {
+            original: program.clone(), source: program, program: optimized, optimized: true,
+            registers: registers.to_vec(), pc: 0, escaped: false
+        }
+    }
+
+    /// Creates a machine that executes every instruction of the `program` as it was written, which is far slower
+    /// than an optimized machine, but useful for checking that the optimizations are sound.
+    pub fn unoptimized(program: Program, registers: &[i64]) -> Machine {
+        Machine {
+            original: program.clone(), source: program.clone(), program: program, optimized: false,
+            registers: registers.to_vec(), pc: 0, escaped: false
+        }
+    }
+
+    /// Rebuilds the executed program from its source after the source has been modified, where the machine
+    /// will continue from the line at `pc`.
+    fn rebuild(&mut self, pc: usize) {
+        self.program = if self.optimized { optimize_at(&self.source, pc) } else { self.source.clone() };
+    }
+
+    /// Returns to the first instruction of the program, undoing any toggles and replacing the registers with
+    /// `registers`.
+    pub fn reset(&mut self, registers: &[i64]) {
+        if self.source != self.original {
+            self.source = self.original.clone();
+            self.rebuild(0);
+        }
+        self.registers.clear();
+        self.registers.extend_from_slice(registers);
+        self.pc = 0;
+        self.escaped = false;
+    }
+
+    /// The program as it was given, before any toggles.
+    pub fn original(&self) -> &[Instruction] { &self.original }
+
+    /// The optimized program that the machine is executing.
+    pub fn program(&self) -> &[Instruction] { &self.program }
+
+    /// The program that the machine is executing, as it would be without optimizations.
+    pub fn source(&self) -> &[Instruction] { &self.source }
+
+    /// The current value of every register, in the order that the registers were declared.
+    pub fn registers(&self) -> &[i64] { &self.registers }
+
+    /// Allows the registers to be modified between steps.
+    pub fn registers_mut(&mut self) -> &mut [i64] { &mut self.registers }
+
+    /// The index of the instruction that will be executed by the next step.
+    pub fn pc(&self) -> usize { self.pc }
+
+    /// The instruction that will be executed by the next step, if the machine has not halted.
+    pub fn current(&self) -> Option<&Instruction> { self.program.get(self.pc) }
+
+    /// Whether the program counter has left the program.
+    pub fn status(&self) -> Status {
+        if self.pc < self.program.len() { Status::Running } else { Status::Halted }
+    }
+
+    /// Executes the current instruction, returning whether the machine may continue. A machine that has
+    /// already halted is left untouched.
+    pub fn step(&mut self) -> Status {
+        let instruction = match self.program.get(self.pc) {
+            Some(&instruction) => instruction,
+            None => return Status::Halted,
+        };
+
+        let registers = &mut self.registers;
+        match instruction {
+            Instruction::Add(x, y) => {
+                registers[y] = registers[y].wrapping_add(registers[x]);
+                registers[x] = 0;
+            },
+            Instruction::Multiply(x, y, scratch, target) => {
+                let x = match x { Kind::Register(x) => registers[x], Kind::Value(x) => x };
+                registers[target] = registers[target].wrapping_add(x.wrapping_mul(registers[y]));
+                registers[scratch] = 0;
+                registers[y] = 0;
+            },
+            Instruction::NoOp | Instruction::Skip(_)    => (),
+            Instruction::CopyInteger(integer, register) => registers[register]  = integer,
+            Instruction::CopyRegister(x, y)             => registers[y]         = registers[x],
+            Instruction::Decrement(register)            => registers[register]  = registers[register].wrapping_sub(1),
+            Instruction::Increment(register)            => registers[register]  = registers[register].wrapping_add(1),
+            Instruction::JumpIf(condition, offset) => if self.value(condition) != 0 {
+                match jump(self.pc, self.value(offset)) {
+                    Some(pc) => self.pc = pc,
+                    None => {
+                        self.pc = self.program.len();
+                        self.escaped = true;
+                    }
+                }
+                return self.status();
+            },
+            Instruction::Toggle(offset) => {
+                let target = jump(self.pc, self.value(offset));
+                if let Some(target) = target.filter(|&target| target < self.source.len()) {
+                    self.source[target] = self.source[target].toggled();
+                    self.rebuild(self.pc + 1);
+                }
+            },
+            Instruction::Out(value) => {
+                self.pc += 1;
+                return Status::Output(self.value(value));
+            },
+        }
+        self.pc += 1;
+        self.status()
+    }
+
+    /// Reads the value of an operand.
+    fn value(&self, kind: Kind) -> i64 {
+        match kind {
+            Kind::Register(register) => self.registers[register],
+            Kind::Value(value)       => value,
+        }
+    }
+
+    /// Executes instructions until the program halts, returning the final registers.
+    pub fn run(&mut self) -> &[i64] {
+        while self.step() != Status::Halted {}
+        &self.registers
+    }
+
+    /// Executes the current instruction, as `step` does, recording it with the `tracer`.
+    pub fn step_traced(&mut self, tracer: &mut Tracer) -> Status {
+        let (pc, instruction) = match self.current() {
+            Some(&instruction) => (self.pc, instruction),
+            None => return Status::Halted,
+        };
+        let status = self.step();
+        tracer.record(pc, instruction, &self.registers);
+        status
+    }
+
+    /// Executes instructions until the program halts, recording each of them with the `tracer`, and returning
+    /// the final registers.
+    pub fn run_traced(&mut self, tracer: &mut Tracer) -> &[i64] {
+        while self.step_traced(tracer) != Status::Halted {}
+        &self.registers
+    }
+
+    /// Executes instructions until the program halts, or until `budget` steps have been executed, if a budget
+    /// is given. Each step is recorded with the `tracer`, if one is given.
+    ///
+    /// A program that returns to a state that it was already in is stopped, as it would otherwise run forever.
+    /// Repeated states are found with Brent's algorithm, which compares the current state against a single
+    /// saved state that is replaced after every power of two steps, so no more than twice the length of the
+    /// loop is executed after the loop is entered, and only one state is ever kept.
+    pub fn run_bounded(&mut self, budget: Option<u64>, mut tracer: Option<&mut Tracer>) -> RunOutcome {
+        let mut saved = self.snapshot();
+        let (mut power, mut length, mut steps) = (1, 0, 0);
+        loop {
+            if budget.is_some_and(|budget| steps >= budget) { return RunOutcome::BudgetExhausted }
+
+            let status = match tracer {
+                Some(ref mut tracer) => self.step_traced(tracer),
+                None                 => self.step(),
+            };
+            steps += 1;
+
+            if status == Status::Halted {
+                return if self.escaped { RunOutcome::JumpOutOfRange } else { RunOutcome::Halted };
+            }
+
+            if self.pc == saved.pc && self.registers == saved.registers && self.source == saved.source {
+                return RunOutcome::LoopDetected;
+            }
+
+            length += 1;
+            if length == power {
+                saved = self.snapshot();
+                power *= 2;
+                length = 0;
+            }
+        }
+    }
+
+    /// Lazily executes the program, yielding each value that it emits until it halts.
+    pub fn outputs(&mut self) -> Outputs<'_> { Outputs { machine: self } }
+
+    /// Captures the state of the machine, which will always execute in the same way from an equal state.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot { pc: self.pc, registers: self.registers.clone(), source: self.source.clone() }
+    }
+}
+
+/// Executes a machine until it emits a value with `out`, yielding that value, or until it halts.
+pub struct Outputs<'a> {
+    machine: &'a mut Machine,
+}
+
+impl<'a> Iterator for Outputs<'a> {
+    type Item = i64;
+    fn next(&mut self) -> Option<i64> {
+        loop {
+            match self.machine.step() {
+                Status::Running       => (),
+                Status::Output(value) => return Some(value),
+                Status::Halted        => return None,
+            }
+        }
+    }
+}
+
+/// Executes the machine until it emits a value, or until the run comes to an end: if it halts, if it returns to a
+/// state that it was already in since the last value was emitted, as it would then loop forever without emitting
+/// another, or if the total number of `steps` taken reaches the `budget`. Repeated states are found with Brent's
+/// algorithm, as `Machine::run_bounded` finds them.
+fn next_output(machine: &mut Machine, steps: &mut u64, budget: Option<u64>) -> Result<i64, RunOutcome> {
+    let mut saved = machine.snapshot();
+    let (mut power, mut length) = (1, 0);
+    loop {
+        if budget.is_some_and(|budget| *steps >= budget) { return Err(RunOutcome::BudgetExhausted) }
+
+        *steps += 1;
+        match machine.step() {
+            Status::Running       => (),
+            Status::Output(value) => return Ok(value),
+            Status::Halted        => return Err(RunOutcome::Halted),
+        }
+
+        if machine.pc == saved.pc && machine.registers == saved.registers && machine.source == saved.source {
+            return Err(RunOutcome::LoopDetected);
+        }
+
+        length += 1;
+        if length == power {
+            saved = machine.snapshot();
+            power *= 2;
+            length = 0;
+        }
+    }
+}
+
+/// Determines whether the machine emits a clock signal of 0, 1, 0, 1... forever. The signal repeats forever
+/// once the machine returns to a state that it was in after emitting an earlier value of the signal, so long
+/// as the same value is expected next. A machine that halts, or that loops forever without emitting another
+/// value, does not emit a clock signal.
+///
+/// A machine that emits a clock signal without ever repeating a state, such as one that counts upwards between
+/// values, can not be decided in this way, so `None` is returned if it is still undecided once the machine has
+/// executed as many steps as the `budget` allows.
+pub fn emits_clock_signal(machine: &mut Machine, budget: Option<u64>) -> Option<bool> {
+    let mut seen = HashSet::new();
+    let (mut expected, mut steps) = (0, 0);
+    loop {
+        match next_output(machine, &mut steps, budget) {
+            Ok(value) => {
+                if value != expected { return Some(false) }
+                expected = 1 - expected;
+                if !seen.insert((machine.snapshot(), expected)) { return Some(true) }
+            },
+            Err(RunOutcome::BudgetExhausted) => return None,
+            Err(_)                           => return Some(false),
+        }
+    }
+}
+
+/// Finds the smallest value of register `a`, from the `candidates` that are given in ascending order, which
+/// makes the `program` emit a clock signal of 0, 1, 0, 1... forever, where every other of the `registers`
+/// begins at zero. There is no such value if none of the registers is named `a`.
+///
+/// Each candidate is given a `budget` of steps in which to be decided, and the first candidate that remains
+/// undecided is returned as an error, as it may yet be the smallest value that emits a clock signal.
+pub fn clock_signal<I: IntoIterator<Item = i64>>(program: &Program, registers: &RegisterFile, candidates: I,
+    budget: Option<u64>) -> Result<Option<i64>, i64>
+{
+    let mut machine = Machine::with_registers(program.clone(), &registers.zeroed());
+    for a in candidates {
+        let values = match registers.values(&[("a", a)]) {
+            Ok(values) => values,
+            Err(_)     => return Ok(None),
+        };
+
+        machine.reset(&values);
+        match emits_clock_signal(&mut machine, budget) {
+            Some(true)  => return Ok(Some(a)),
+            Some(false) => (),
+            None        => return Err(a),
+        }
+    }
+    Ok(None)
+}
+
+#[test]
+fn stepping() {
+    let program = super::parse_program("cpy 41 a\ninc a\ninc a\ndec a\njnz a 2\ndec a").unwrap();
+    let mut machine = Machine::new(program);
+    assert_eq!(Some(&Instruction::CopyInteger(41, 0)), machine.current());
+    assert_eq!(Status::Running, machine.step());
+    assert_eq!((1, 41), (machine.pc(), machine.registers()[0]));
+    for _ in 0..3 { machine.step(); }
+    assert_eq!((4, 42), (machine.pc(), machine.registers()[0]));
+    assert_eq!(Status::Halted, machine.step());
+    assert_eq!(None, machine.current());
+    assert_eq!(Status::Halted, machine.step());
+    assert_eq!(42, machine.registers()[0]);
+
+    machine.reset(&[0, 0, 0, 0]);
+    assert_eq!(&[42, 0, 0, 0], machine.run());
+}
+
+#[test]
+fn toggling() {
+    let program = super::parse_program("cpy 2 a\ntgl a\ntgl a\ntgl a\ncpy 1 a\ndec a\ndec a").unwrap();
+    let mut machine = Machine::new(program);
+    assert_eq!(3, machine.run()[0]);
+    machine.reset(&[0; 4]);
+    assert_eq!(3, machine.run()[0]);
+
+    // The `inc a` of the add loop is toggled into a `dec a` before the loop runs.
+    let program = super::parse_program("cpy 2 c\ntgl c\ncpy 3 b\ninc a\ndec b\njnz b -2").unwrap();
+    assert_eq!(Instruction::Add(1, 0), Machine::new(program.clone()).program()[5]);
+    assert_eq!(-3, Machine::new(program).run()[0]);
+}
+
+#[test]
+fn bounded() {
+    let run = |source: &str, budget: Option<u64>| {
+        let mut machine = Machine::new(super::parse_program(source).unwrap());
+        (machine.run_bounded(budget, None), machine.registers()[0])
+    };
+
+    assert_eq!((RunOutcome::Halted, 42), run("cpy 41 a\ninc a\ninc a\ndec a\njnz a 2\ndec a", Some(10)));
+    assert_eq!((RunOutcome::LoopDetected, 0), run("jnz 1 0", None));
+    assert_eq!((RunOutcome::LoopDetected, 1), run("inc a\ncpy 5 b\ndec b\ncpy 5 b\njnz 1 -2", None));
+    assert_eq!((RunOutcome::JumpOutOfRange, 1), run("inc a\njnz 1 -5\ninc a", None));
+    assert_eq!((RunOutcome::BudgetExhausted, 3), run("inc a\njnz 1 -1", Some(6)));
+    assert_eq!((RunOutcome::Halted, 0), run("jnz 1 9223372036854775807\ninc a", None));
+}
+
+#[test]
+fn clock_signals() {
+    // Emits the bits of `a + 7`, from the least significant bit, over and over.
+    let program = super::parse_program("cpy a d\ncpy 7 c\ninc d\ndec c\njnz c -2\ncpy d a\ncpy a b\ncpy 0 a\n\
+        cpy 2 c\njnz b 2\njnz 1 6\ndec b\ndec c\njnz c -4\ninc a\njnz 1 -7\ncpy 2 b\njnz c 2\njnz 1 4\n\
+        dec b\ndec c\njnz 1 -4\nout b\njnz a -17\njnz 1 -19").unwrap();
+    let mut machine = Machine::with_registers(program.clone(), &[6, 0, 0, 0]);
+    assert_eq!(vec![1, 0, 1, 1, 1, 0, 1], machine.outputs().take(7).collect::<Vec<i64>>());
+    assert_eq!(Ok(Some(3)), clock_signal(&program, &RegisterFile::default(), 0.., Some(100_000)));
+
+    let program = super::parse_program("out 0\nout 1\nout 0").unwrap();
+    assert_eq!(vec![0, 1, 0], Machine::new(program.clone()).outputs().collect::<Vec<i64>>());
+    assert_eq!(Ok(None), clock_signal(&program, &RegisterFile::default(), 0..10, None));
+
+    // Loops forever without emitting anything.
+    let program = super::parse_program("jnz 1 0").unwrap();
+    assert_eq!(Ok(None), clock_signal(&program, &RegisterFile::default(), 0..3, None));
+    let program = super::parse_program("out 0\nout 1\ncpy 5 b\ninc b\njnz 1 -2").unwrap();
+    assert_eq!(Ok(None), clock_signal(&program, &RegisterFile::default(), 0..3, None));
+
+    // Only `a` is given a value, however many registers the machine has.
+    let registers = RegisterFile::new(&["x", "a", "y", "z", "w"]).unwrap();
+    let program = super::parse_program_with("cpy a x\nout x\ninc x\nout x\njnz 1 -4", &registers).unwrap();
+    assert_eq!(Ok(Some(0)), clock_signal(&program, &registers, -5..5, None));
+    let registers = RegisterFile::new(&["x"]).unwrap();
+    let program = super::parse_program_with("out 0\nout 1\njnz 1 -2", &registers).unwrap();
+    assert_eq!(Ok(None), clock_signal(&program, &registers, 0..3, None));
+
+    // Emits a clock signal while counting upwards, and so never repeats a state.
+    let program = super::parse_program("out 0\nout 1\ninc b\njnz 1 -3").unwrap();
+    assert_eq!(None, emits_clock_signal(&mut Machine::new(program.clone()), Some(1000)));
+    assert_eq!(Err(0), clock_signal(&program, &RegisterFile::default(), 0..3, Some(1000)));
+    let program = super::parse_program("out 1\ninc b\njnz 1 -2").unwrap();
+    assert_eq!(Some(false), emits_clock_signal(&mut Machine::new(program), Some(1000)));
+}
@@ -0,0 +1,26 @@
+//! A virtual machine for assembunny, the assembly language of the Easter Bunny's monorail computer, which
+//! can be driven one instruction at a time or run until the program halts.
+pub mod analyzer;
+pub mod compiler;
+pub mod debugger;
+pub mod disassembler;
+pub mod generator;
+pub mod instruction;
+pub mod machine;
+pub mod optimizer;
+pub mod registers;
+pub mod tracer;
+
+pub use self::instruction::{parse_instruction, parse_program, parse_program_with, parse_program_with_limit};
+pub use self::instruction::{ErrorKind, Instruction, InstructionIter, Invalid, Kind, Named};
+pub use self::analyzer::{analyze, Analysis, Lint};
+pub use self::compiler::Compiled;
+pub use self::debugger::Debugger;
+pub use self::disassembler::{disassemble, format_program};
+pub use self::machine::{clock_signal, emits_clock_signal, Machine, Outputs, RunOutcome, Snapshot, Status};
+pub use self::optimizer::{optimize, optimize_at};
+pub use self::registers::{RegisterError, RegisterFile, DEFAULT_NAMES};
+pub use self::tracer::Tracer;
+
+/// A parsed assembunny program.
+pub type Program = Vec<Instruction>;
@@ -0,0 +1,180 @@
+//! A peephole optimizer, which replaces loops that only exist to perform arithmetic with a single instruction
+//! that performs the same arithmetic. Every loop is replaced by `NoOp`s followed by the optimized instruction,
+//! so the program keeps its length and every jump offset remains valid.
+//!
+//! Each loop is assumed to count down from a positive value, as a loop that counts down from zero or below
+//! would otherwise run until its counter overflows.
+//!
+//! A loop is only replaced when it can only be entered from its first instruction, as a jump into the middle
+//! of a loop would otherwise land on a `NoOp`. As the target of a jump by a register is not known until the
+//! program runs, programs that contain one are not optimized at all.
+use super::{Instruction, Kind, Program};
+use super::machine::jump;
+
+/// Replaces add, multiply, and zeroing loops within the program with the instructions that they perform.
+pub fn optimize(program: &[Instruction]) -> Program { optimize_at(program, 0) }
+
+/// Optimizes the program as `optimize` does, for a machine that will continue from the line at `pc`, which is
+/// treated as though it were the target of a jump, as a program that has been toggled while it runs may
+/// otherwise continue from the middle of a loop that has only now become one.
+pub fn optimize_at(program: &[Instruction], pc: usize) -> Program {
+    if program.iter().any(|instruction| matches!(*instruction, Instruction::JumpIf(_, Kind::Register(_)))) {
+        return program.to_vec();
+    }
+
+    // Every jump, from the line that it is on to the line that it lands on.
+    let jumps = program.iter().enumerate().filter_map(|(pc, instruction)| match *instruction {
+        Instruction::JumpIf(_, Kind::Value(offset)) => jump(pc, offset).map(|target| (pc, target)),
+        _ => None,
+    }).chain(Some((usize::MAX, pc))).collect::<Vec<(usize, usize)>>();
+
+    let mut output = Program::with_capacity(program.len());
+    let mut index = 0;
+    while index < program.len() {
+        let window = &program[index..];
+        let (instruction, length) = multiply_loop(window).or_else(|| add_loop(window))
+            .or_else(|| zero_loop(window)).filter(|&(_, length)| !entered(&jumps, index, length))
+            .unwrap_or((program[index], 1));
+        for _ in 1..length { output.push(Instruction::NoOp); }
+        output.push(instruction);
+        index += length;
+    }
+    output
+}
+
+/// Whether any jump from outside of the `length` lines beginning at `start` lands after the first of them.
+fn entered(jumps: &[(usize, usize)], start: usize, length: usize) -> bool {
+    let within = |line: usize| line >= start && line < start + length;
+    jumps.iter().any(|&(pc, target)| !within(pc) && within(target) && target != start)
+}
+
+/// `inc x; dec y; jnz y -2`, or `dec y; inc x; jnz y -2`, adds `y` to `x` and then sets `y` to zero.
+fn add_loop(window: &[Instruction]) -> Option<(Instruction, usize)> {
+    let (x, y) = match *window {
+        [Instruction::Increment(x), Instruction::Decrement(y), ..] |
+        [Instruction::Decrement(y), Instruction::Increment(x), ..] => (x, y),
+        _ => return None,
+    };
+
+    match window.get(2) {
+        Some(&Instruction::JumpIf(Kind::Register(z), Kind::Value(-2))) if x != y && y == z => {
+            Some((Instruction::Add(y, x), 3))
+        },
+        _ => None,
+    }
+}
+
+/// `cpy b c`, followed by an add loop that adds `c` to `a`, and then by `dec d; jnz d -5`, adds `b * d` to
+/// `a` and then sets both `c` and `d` to zero.
+fn multiply_loop(window: &[Instruction]) -> Option<(Instruction, usize)> {
+    let (factor, scratch) = match window.first() {
+        Some(&Instruction::CopyInteger(value, register))  => (Kind::Value(value), register),
+        Some(&Instruction::CopyRegister(source, register)) => (Kind::Register(source), register),
+        _ => return None,
+    };
+
+    let target = match add_loop(&window[1..]) {
+        Some((Instruction::Add(counter, target), _)) if counter == scratch => target,
+        _ => return None,
+    };
+
+    match window.get(4..6) {
+        Some(&[Instruction::Decrement(counter), Instruction::JumpIf(Kind::Register(condition), Kind::Value(-5))])
+            if counter == condition && counter != scratch && counter != target
+                && factor != Kind::Register(counter) && factor != Kind::Register(target)
+                && factor != Kind::Register(scratch) =>
+        {
+            Some((Instruction::Multiply(factor, counter, scratch, target), 6))
+        },
+        _ => None,
+    }
+}
+
+/// `dec x; jnz x -1` sets `x` to zero.
+fn zero_loop(window: &[Instruction]) -> Option<(Instruction, usize)> {
+    match *window {
+        [Instruction::Decrement(x), Instruction::JumpIf(Kind::Register(y), Kind::Value(-1)), ..] if x == y => {
+            Some((Instruction::CopyInteger(0, x), 2))
+        },
+        _ => None,
+    }
+}
+
+#[test]
+fn loops() {
+    let program = super::parse_program("cpy 2 a\ninc a\ndec b\njnz b -2\ndec b\ninc a\njnz b -2\ninc c\n\
+        dec d").unwrap();
+    let expected = [
+        Instruction::CopyInteger(2, 0),
+        Instruction::NoOp,
+        Instruction::NoOp,
+        Instruction::Add(1, 0),
+        Instruction::NoOp,
+        Instruction::NoOp,
+        Instruction::Add(1, 0),
+        Instruction::Increment(2),
+        Instruction::Decrement(3),
+    ];
+    assert_eq!(&expected[..], &optimize(&program)[..]);
+
+    let program = super::parse_program("cpy b c\ninc a\ndec c\njnz c -2\ndec d\njnz d -5\ndec b\njnz b -1").unwrap();
+    let optimized = optimize(&program);
+    assert_eq!(Instruction::Multiply(Kind::Register(1), 3, 2, 0), optimized[5]);
+    assert_eq!(Instruction::CopyInteger(0, 1), optimized[7]);
+
+    // The factor must not be modified by the loop that it is multiplied within.
+    let program = super::parse_program("cpy d c\ninc a\ndec c\njnz c -2\ndec d\njnz d -5").unwrap();
+    assert_eq!(Instruction::Add(2, 0), optimize(&program)[3]);
+}
+
+#[test]
+fn equivalence() {
+    use super::{Compiled, Machine};
+
+    let samples: &[(&str, [i64; 4])] = &[
+        ("cpy 41 a\ninc a\ninc a\ndec a\njnz a 2\ndec a", [0; 4]),
+        (include_str!("../12/input.txt"), [0; 4]),
+        ("cpy 2 a\ntgl a\ntgl a\ntgl a\ncpy 1 a\ndec a\ndec a", [0; 4]),
+        ("cpy 2 c\ntgl c\ncpy 3 b\ninc a\ndec b\njnz b -2", [0; 4]),
+        ("cpy 3 b\ncpy 4 d\ncpy b c\ninc a\ndec c\njnz c -2\ndec d\njnz d -5", [5, 0, 0, 0]),
+        ("cpy b c\ndec c\ninc a\njnz c -2\ndec d\njnz d -5\ndec b\njnz b -1", [0, 9, 0, 3]),
+        ("cpy a b\ndec b\ncpy a d\ncpy 0 a\ncpy b c\ninc a\ndec c\njnz c -2\ndec d\njnz d -5\ndec b\ncpy b c\n\
+          cpy c d\ndec d\ninc c\njnz d -2\ntgl c\ncpy -16 c\njnz 1 c\ncpy 73 c\njnz 71 d\ninc a\ninc d\njnz d -2\n\
+          inc c\njnz c -5", [7, 0, 0, 0]),
+        // The fused multiplication and addition wrap around, as the increments that they replace do.
+        ("cpy 3 b\ncpy 4 d\ncpy b c\ninc a\ndec c\njnz c -2\ndec d\njnz d -5", [i64::MAX - 5, 0, 0, 0]),
+        ("cpy 3 b\ninc a\ndec b\njnz b -2", [i64::MAX - 1, 0, 0, 0]),
+    ];
+
+    for &(source, registers) in samples {
+        let program = super::parse_program(source).unwrap();
+        let optimized = Machine::with_registers(program.clone(), &registers).run().to_vec();
+        let compiled = Compiled::new(program.clone()).run(&registers, None).1;
+        let unoptimized = Machine::unoptimized(program, &registers).run().to_vec();
+        assert_eq!(unoptimized, optimized, "{}", source);
+        assert_eq!(unoptimized, compiled, "{}", source);
+    }
+
+    // The `tgl` becomes `inc a`, completing an add loop that the machine is already within.
+    let program = super::parse_program("cpy 3 b\ntgl a\ndec b\njnz b -2").unwrap();
+    assert_eq!(2, Machine::new(program.clone()).run()[0]);
+    assert_eq!(2, Machine::unoptimized(program, &[0; 4]).run()[0]);
+}
+
+#[test]
+fn entered_loops() {
+    use super::Machine;
+
+    // The loop is entered at its `dec`, and so must not be replaced by an addition.
+    let program = super::parse_program("cpy 3 b\njnz 1 2\ninc a\ndec b\njnz b -2").unwrap();
+    assert_eq!(&program[..], &optimize(&program)[..]);
+    assert_eq!(2, Machine::new(program.clone()).run()[0]);
+    assert_eq!(2, super::generator::reference(&program, &[0; 4], 100).1[0]);
+
+    // The loop is only ever entered from its first instruction.
+    let program = super::parse_program("cpy 3 b\njnz 1 1\ninc a\ndec b\njnz b -2").unwrap();
+    assert_eq!(Instruction::Add(1, 0), optimize(&program)[4]);
+
+    let program = super::parse_program("cpy 3 b\ncpy 2 c\njnz 1 c\ninc a\ndec b\njnz b -2").unwrap();
+    assert_eq!(&program[..], &optimize(&program)[..]);
+}
@@ -0,0 +1,122 @@
+//! The registers that a machine is declared with, which decide the names that a program may refer to, and
+//! allow the initial value of each register to be given by its name.
+use std::fmt::{self, Display};
+
+/// The names of the registers that the Easter Bunny's computer has.
+pub const DEFAULT_NAMES: [&'static str; 4] = ["a", "b", "c", "d"];
+
+/// The names of the registers of a machine, where each register is referred to by the index of its name.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegisterFile {
+    names: Vec<String>,
+}
+
+impl Default for RegisterFile {
+    fn default() -> RegisterFile { RegisterFile::new(&DEFAULT_NAMES).expect("the default names are valid") }
+}
+
+/// Why a register file could not be declared, or why initial values could not be assigned to its registers.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RegisterError {
+    /// The name is empty, is an integer, or contains whitespace, `=`, or `,`, and so could not be parsed.
+    InvalidName(String),
+    /// The name was given to more than one register.
+    DuplicateName(String),
+    /// No register has the name.
+    UnknownRegister(String),
+    /// The assignment is not of the form `NAME=VALUE`.
+    MalformedAssignment(String),
+}
+
+impl Display for RegisterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RegisterError::InvalidName(ref name)      => write!(f, "'{}' is not a valid register name", name),
+            RegisterError::DuplicateName(ref name)    => write!(f, "the register '{}' is declared twice", name),
+            RegisterError::UnknownRegister(ref name)  => write!(f, "there is no register named '{}'", name),
+            RegisterError::MalformedAssignment(ref x) => write!(f, "expected NAME=VALUE, but found '{}'", x),
+        }
+    }
+}
+
+impl RegisterFile {
+    /// Declares a register for each of the `names`, in order.
+    pub fn new<S: AsRef<str>>(names: &[S]) -> Result<RegisterFile, RegisterError> {
+        let mut file = RegisterFile { names: Vec::with_capacity(names.len()) };
+        for name in names.iter().map(AsRef::as_ref) {
+            let separator = |c: char| c.is_whitespace() || c == '=' || c == ',';
+            if name.is_empty() || name.parse::<i64>().is_ok() || name.contains(separator) {
+                return Err(RegisterError::InvalidName(String::from(name)));
+            } else if file.index(name).is_some() {
+                return Err(RegisterError::DuplicateName(String::from(name)));
+            }
+            file.names.push(String::from(name));
+        }
+        Ok(file)
+    }
+
+    /// The number of registers.
+    pub fn len(&self) -> usize { self.names.len() }
+
+    /// Whether there are no registers at all.
+    pub fn is_empty(&self) -> bool { self.names.is_empty() }
+
+    /// The index of the register with the given name.
+    pub fn index(&self, name: &str) -> Option<usize> { self.names.iter().position(|x| x == name) }
+
+    /// The name of the register at `index`.
+    pub fn name(&self, index: usize) -> &str { &self.names[index] }
+
+    /// A value for every register, each set to zero.
+    pub fn zeroed(&self) -> Vec<i64> { vec![0; self.names.len()] }
+
+    /// A value for every register, where the registers that are named by the `assignments` are given their
+    /// values, and every other register is zero.
+    pub fn values(&self, assignments: &[(&str, i64)]) -> Result<Vec<i64>, RegisterError> {
+        let mut values = self.zeroed();
+        for &(name, value) in assignments {
+            match self.index(name) {
+                Some(index) => values[index] = value,
+                None        => return Err(RegisterError::UnknownRegister(String::from(name))),
+            }
+        }
+        Ok(values)
+    }
+
+    /// A value for every register, from assignments such as `c=1` or `a=7,c=1`, which may be separated by
+    /// commas or whitespace. Every register that is not assigned is zero.
+    pub fn assign(&self, assignments: &str) -> Result<Vec<i64>, RegisterError> {
+        let mut parsed = Vec::new();
+        for assignment in assignments.split(|c: char| c == ',' || c.is_whitespace()).filter(|x| !x.is_empty()) {
+            let malformed = || RegisterError::MalformedAssignment(String::from(assignment));
+            let mut parts = assignment.splitn(2, '=');
+            let name = parts.next().ok_or_else(&malformed)?;
+            let value = parts.next().and_then(|value| value.parse::<i64>().ok()).ok_or_else(&malformed)?;
+            parsed.push((name, value));
+        }
+        self.values(&parsed)
+    }
+
+    /// Describes the `values` of the registers, such as `a=0 b=0 c=1 d=0`.
+    pub fn describe(&self, values: &[i64]) -> String {
+        self.names.iter().zip(values).map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<String>>().join(" ")
+    }
+}
+
+#[test]
+fn assignments() {
+    let file = RegisterFile::default();
+    assert_eq!(Ok(vec![0, 0, 1, 0]), file.assign("c=1"));
+    assert_eq!(Ok(vec![7, 0, -1, 0]), file.assign("a=7, c=-1"));
+    assert_eq!(Ok(vec![0; 4]), file.assign(""));
+    assert_eq!(Err(RegisterError::UnknownRegister(String::from("e"))), file.assign("e=1"));
+    assert_eq!(Err(RegisterError::MalformedAssignment(String::from("c"))), file.assign("a=1 c"));
+    assert_eq!("a=0 b=0 c=1 d=0", file.describe(&file.assign("c=1").unwrap()));
+
+    let file = RegisterFile::new(&["x", "y", "acc"]).unwrap();
+    assert_eq!((3, Some(2)), (file.len(), file.index("acc")));
+    assert_eq!(Ok(vec![0, 2, 0]), file.values(&[("y", 2)]));
+    assert_eq!(Err(RegisterError::DuplicateName(String::from("x"))), RegisterFile::new(&["x", "x"]));
+    assert_eq!(Err(RegisterError::InvalidName(String::from("12"))), RegisterFile::new(&["12"]));
+}
@@ -0,0 +1,114 @@
+//! Records how often each instruction of a program is executed, so that the hottest lines of a program,
+//! and the loops that the optimizer should target next, can be found.
+use super::Instruction;
+use output::escape_json;
+use std::collections::VecDeque;
+
+/// A single step of the program, as it was recorded by a tracer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    /// The index of the instruction that was executed.
+    pub pc:          usize,
+    pub instruction: Instruction,
+    /// The registers after the instruction was executed.
+    pub registers:   Vec<i64>,
+}
+
+/// Counts the steps that a machine takes, along with how many times each instruction was executed, and
+/// optionally keeps a history of the most recent steps.
+#[derive(Clone, Debug, Default)]
+pub struct Tracer {
+    counts:   Vec<u64>,
+    steps:    u64,
+    history:  VecDeque<Entry>,
+    capacity: usize,
+}
+
+impl Tracer {
+    /// Creates a tracer that only counts executions, without keeping a history.
+    pub fn new() -> Tracer { Tracer::default() }
+
+    /// Creates a tracer that also keeps the most recent `capacity` steps.
+    pub fn with_history(capacity: usize) -> Tracer {
+        Tracer { history: VecDeque::with_capacity(capacity), capacity: capacity, ..Tracer::default() }
+    }
+
+    /// Records that the `instruction` at `pc` was executed, leaving the machine with the given `registers`.
+    pub fn record(&mut self, pc: usize, instruction: Instruction, registers: &[i64]) {
+        if pc >= self.counts.len() { self.counts.resize(pc + 1, 0); }
+        self.counts[pc] += 1;
+        self.steps += 1;
+
+        if self.capacity != 0 {
+            if self.history.len() == self.capacity { self.history.pop_front(); }
+            self.history.push_back(Entry { pc: pc, instruction: instruction, registers: registers.to_vec() });
+        }
+    }
+
+    /// The total number of instructions that were executed.
+    pub fn steps(&self) -> u64 { self.steps }
+
+    /// The number of times that the instruction at `pc` was executed.
+    pub fn count(&self, pc: usize) -> u64 { self.counts.get(pc).cloned().unwrap_or(0) }
+
+    /// The most recent steps, from the oldest to the newest.
+    pub fn history(&self) -> &VecDeque<Entry> { &self.history }
+
+    /// Describes the trace as text, listing the execution count of every instruction of the `program`, and
+    /// then the history, if one was kept.
+    pub fn to_text(&self, program: &[Instruction]) -> String {
+        let mut output = format!("steps: {}\nline        count  instruction\n", self.steps);
+        for (pc, instruction) in program.iter().enumerate() {
+            output.push_str(&format!("{:>4} {:>12}  {:?}\n", pc + 1, self.count(pc), instruction));
+        }
+
+        if self.capacity != 0 {
+            output.push_str(&format!("last {} steps:\n", self.history.len()));
+            for entry in &self.history {
+                output.push_str(&format!("{:>4}  {:<32}  {:?}\n", entry.pc + 1, format!("{:?}", entry.instruction),
+                    entry.registers));
+            }
+        }
+        output
+    }
+
+    /// Describes the trace as a JSON object, such as
+    /// `{"steps":3,"counts":[{"line":1,"instruction":"Increment(0)","count":3}],"history":[]}`.
+    pub fn to_json(&self, program: &[Instruction]) -> String {
+        let counts = program.iter().enumerate().map(|(pc, instruction)| {
+            format!(r#"{{"line":{},"instruction":"{}","count":{}}}"#, pc + 1,
+                escape_json(&format!("{:?}", instruction)), self.count(pc))
+        }).collect::<Vec<String>>();
+
+        let history = self.history.iter().map(|entry| {
+            let registers = entry.registers.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+            format!(r#"{{"line":{},"instruction":"{}","registers":[{}]}}"#, entry.pc + 1,
+                escape_json(&format!("{:?}", entry.instruction)), registers.join(","))
+        }).collect::<Vec<String>>();
+
+        format!(r#"{{"steps":{},"counts":[{}],"history":[{}]}}"#, self.steps, counts.join(","), history.join(","))
+    }
+}
+
+#[test]
+fn tracing() {
+    use super::{parse_program, Machine};
+
+    let program = parse_program("cpy 3 b\ninc a\ndec b\njnz b -2\ndec a").unwrap();
+    let mut tracer = Tracer::with_history(2);
+    let mut machine = Machine::unoptimized(program.clone(), &[0; 4]);
+    assert_eq!(2, machine.run_traced(&mut tracer)[0]);
+    assert_eq!((11, 1, 3, 3, 1), (tracer.steps(), tracer.count(0), tracer.count(1), tracer.count(3), tracer.count(4)));
+    assert_eq!(vec![3, 4], tracer.history().iter().map(|entry| entry.pc).collect::<Vec<usize>>());
+    assert_eq!(vec![2, 0, 0, 0], tracer.history()[1].registers);
+
+    let json = tracer.to_json(&program);
+    assert!(json.starts_with(r#"{"steps":11,"counts":[{"line":1,"instruction":"CopyInteger(3, 1)","count":1},"#));
+    assert!(json.ends_with(r#"{"line":5,"instruction":"Decrement(0)","registers":[2,0,0,0]}]}"#));
+    assert!(tracer.to_text(&program).starts_with("steps: 11\nline        count  instruction\n   1            1  Copy"));
+
+    // Without a history, only the executions are counted.
+    let mut tracer = Tracer::new();
+    Machine::new(program).run_traced(&mut tracer);
+    assert_eq!((5, 0), (tracer.steps(), tracer.history().len()));
+}
@@ -0,0 +1,218 @@
+use parse::ParseError;
+use solution::Solution;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::fs::File;
+use std::hint::black_box;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use time;
+
+/// The header row that begins every baseline file.
+const HEADER: &'static str = "day\tphase\tmin_ns\tmedian_ns\tp95_ns";
+
+/// How many times each phase of a solver is run.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Config {
+    /// Runs that are discarded before measuring, so that caches and the allocator have settled.
+    pub warmup:     usize,
+    /// Runs that are measured.
+    pub iterations: usize,
+}
+
+impl Default for Config {
+    fn default() -> Config { Config { warmup: 1, iterations: 10 } }
+}
+
+/// The phases of a solver that are measured separately.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase { Parse, PartOne, PartTwo }
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self { Phase::Parse => "parse", Phase::PartOne => "part1", Phase::PartTwo => "part2" })
+    }
+}
+
+impl FromStr for Phase {
+    type Err = ();
+    fn from_str(input: &str) -> Result<Phase, ()> {
+        match input {
+            "parse" => Ok(Phase::Parse),
+            "part1" => Ok(Phase::PartOne),
+            "part2" => Ok(Phase::PartTwo),
+            _       => Err(()),
+        }
+    }
+}
+
+/// The distribution of the time, in nanoseconds, that a phase took across every measured run.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Summary {
+    pub min:    u64,
+    pub median: u64,
+    pub p95:    u64,
+}
+
+impl Summary {
+    /// Summarizes the samples, which must not be empty, using the nearest-rank method for each percentile.
+    pub fn from_samples(samples: &mut [u64]) -> Summary {
+        samples.sort();
+        let rank = |percentile: usize| samples[(samples.len() * percentile).div_ceil(100).max(1) - 1];
+        Summary { min: samples[0], median: rank(50), p95: rank(95) }
+    }
+}
+
+/// The summary of each phase that was measured, in the order that the phases were requested.
+pub type Measurements = Vec<(Phase, Summary)>;
+
+/// Times the `iterations` runs of `phase` that follow its `warmup` runs.
+pub fn measure<T, F: FnMut() -> T>(config: Config, mut phase: F) -> Summary {
+    for _ in 0..config.warmup { black_box(phase()); }
+    let mut samples = Vec::with_capacity(config.iterations);
+    for _ in 0..config.iterations.max(1) {
+        let begin = time::precise_time_ns();
+        black_box(phase());
+        samples.push(time::precise_time_ns() - begin);
+    }
+    Summary::from_samples(&mut samples)
+}
+
+/// Measures the requested phases of a solution, in order, where each part is given the same parsed input.
+/// Nothing is measured if the input cannot be parsed.
+pub fn benchmark<S: Solution>(input: &str, phases: &[Phase], config: Config)
+    -> Result<Measurements, ParseError<S::Error>>
+{
+    let parsed = S::parse(input)?;
+    Ok(phases.iter().map(|&phase| (phase, match phase {
+        Phase::Parse   => measure(config, || S::parse(black_box(input))),
+        Phase::PartOne => measure(config, || S::part_one(black_box(&parsed))),
+        Phase::PartTwo => measure(config, || S::part_two(black_box(&parsed))),
+    })).collect())
+}
+
+/// How a measurement compares to the baseline's measurement of the same phase.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Comparison {
+    /// The phase was not in the baseline.
+    New,
+    /// The median changed by the given percentage, and is within the allowed threshold.
+    Within(f64),
+    /// The median grew by the given percentage, which exceeds the allowed threshold.
+    Regressed(f64),
+}
+
+/// Errors that may occur while loading a baseline.
+#[derive(Debug)]
+pub enum BaselineError {
+    /// The baseline could not be read.
+    Io(PathBuf, io::Error),
+    /// The line, counting from one, is not a valid baseline entry.
+    Malformed(usize, String),
+}
+
+impl Display for BaselineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BaselineError::Io(ref path, ref why) => write!(f, "unable to read '{}': {}", path.display(), why),
+            BaselineError::Malformed(line, ref text) => write!(f, "line {} is not a valid entry: '{}'", line, text),
+        }
+    }
+}
+
+impl Error for BaselineError {}
+
+/// Previously-saved measurements that new measurements are compared against.
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline { entries: BTreeMap<(u8, Phase), Summary> }
+
+impl Baseline {
+    /// Parses a baseline of tab-separated `day`, `phase`, `min_ns`, `median_ns`, and `p95_ns` columns,
+    /// after a header row.
+    pub fn parse(input: &str) -> Result<Baseline, BaselineError> {
+        let mut baseline = Baseline::default();
+        for (id, line) in input.lines().enumerate() {
+            if line.is_empty() || (id == 0 && line == HEADER) { continue }
+            let malformed = || BaselineError::Malformed(id + 1, String::from(line));
+            let mut fields = line.split('\t');
+            let day    = fields.next().and_then(|x| x.parse::<u8>().ok()).ok_or_else(&malformed)?;
+            let phase  = fields.next().and_then(|x| x.parse::<Phase>().ok()).ok_or_else(&malformed)?;
+            let min    = fields.next().and_then(|x| x.parse::<u64>().ok()).ok_or_else(&malformed)?;
+            let median = fields.next().and_then(|x| x.parse::<u64>().ok()).ok_or_else(&malformed)?;
+            let p95    = fields.next().and_then(|x| x.parse::<u64>().ok()).ok_or_else(&malformed)?;
+            if fields.next().is_some() { return Err(malformed()); }
+            baseline.entries.insert((day, phase), Summary { min: min, median: median, p95: p95 });
+        }
+        Ok(baseline)
+    }
+
+    /// Loads the baseline at `path`.
+    pub fn load(path: &Path) -> Result<Baseline, BaselineError> {
+        let mut input = String::new();
+        File::open(path).and_then(|mut file| file.read_to_string(&mut input))
+            .map_err(|why| BaselineError::Io(path.to_path_buf(), why))?;
+        Baseline::parse(&input)
+    }
+
+    /// Writes the baseline to `path`, replacing the previous baseline.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        File::create(path).and_then(|mut file| file.write_all(self.to_string().as_bytes()))
+    }
+
+    /// Records the `summary` as the baseline for a day's phase.
+    pub fn insert(&mut self, day: u8, phase: Phase, summary: Summary) { self.entries.insert((day, phase), summary); }
+
+    /// Compares the median of a new `summary` against the baseline, where `threshold` is the percentage
+    /// by which the median may grow before it is considered to have regressed.
+    pub fn compare(&self, day: u8, phase: Phase, summary: &Summary, threshold: f64) -> Comparison {
+        match self.entries.get(&(day, phase)) {
+            Some(baseline) => {
+                let change = (summary.median as f64 - baseline.median as f64) / (baseline.median.max(1) as f64) * 100.0;
+                if change > threshold { Comparison::Regressed(change) } else { Comparison::Within(change) }
+            },
+            None => Comparison::New,
+        }
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+        for (&(day, phase), summary) in &self.entries {
+            writeln!(f, "{}\t{}\t{}\t{}\t{}", day, phase, summary.min, summary.median, summary.p95)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn percentiles() {
+    let mut samples = (1..101).rev().collect::<Vec<u64>>();
+    assert_eq!(Summary { min: 1, median: 50, p95: 95 }, Summary::from_samples(&mut samples));
+    assert_eq!(Summary { min: 7, median: 7, p95: 7 }, Summary::from_samples(&mut [7]));
+    assert_eq!(Summary { min: 1, median: 2, p95: 3 }, Summary::from_samples(&mut [3, 1, 2]));
+}
+
+#[test]
+fn baseline_comparison() {
+    let mut baseline = Baseline::default();
+    baseline.insert(7, Phase::PartOne, Summary { min: 90, median: 100, p95: 120 });
+    let baseline = Baseline::parse(&baseline.to_string()).unwrap();
+
+    let faster = Summary { min: 80, median: 95, p95: 100 };
+    let slower = Summary { min: 100, median: 125, p95: 150 };
+    assert_eq!(Comparison::Within(-5.0), baseline.compare(7, Phase::PartOne, &faster, 10.0));
+    assert_eq!(Comparison::Regressed(25.0), baseline.compare(7, Phase::PartOne, &slower, 10.0));
+    assert_eq!(Comparison::New, baseline.compare(7, Phase::PartTwo, &slower, 10.0));
+    assert!(Baseline::parse("7\tpart3\t1\t2\t3").is_err());
+}
+
+#[test]
+fn benchmark_phases() {
+    let config = Config { warmup: 0, iterations: 3 };
+    let phases = benchmark::<::day07::Day07>("abba[mnop]qrst", &[Phase::Parse, Phase::PartTwo], config).unwrap();
+    assert_eq!(vec![Phase::Parse, Phase::PartTwo], phases.iter().map(|&(phase, _)| phase).collect::<Vec<_>>());
+    assert!(phases.iter().all(|(_, summary)| summary.min <= summary.median && summary.median <= summary.p95));
+}
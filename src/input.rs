@@ -0,0 +1,142 @@
+use solution::Solution;
+use std::env;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Where the puzzle input for a day is read from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    /// Read the input from the file at the given path.
+    File(PathBuf),
+    /// Read the input from the standard input.
+    Stdin,
+    /// Read the input from the contents of the named environment variable.
+    Env(String),
+}
+
+/// Errors that may occur while loading puzzle input.
+#[derive(Debug)]
+pub enum InputError {
+    /// The input file does not exist.
+    Missing(PathBuf),
+    /// The environment variable is not set, or is not valid unicode.
+    Unset(String),
+    /// The input was read, but it contains nothing other than whitespace.
+    Empty(Source),
+    /// The input could not be read.
+    Io(Source, io::Error),
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Source::File(ref path)    => write!(f, "'{}'", path.display()),
+            Source::Stdin             => f.write_str("standard input"),
+            Source::Env(ref variable) => write!(f, "${}", variable),
+        }
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InputError::Missing(ref path)       => write!(f, "there is no input file at '{}'", path.display()),
+            InputError::Unset(ref variable)     => write!(f, "the ${} environment variable is not set", variable),
+            InputError::Empty(ref source)       => write!(f, "the input from {} is empty", source),
+            InputError::Io(ref source, ref why) => write!(f, "unable to read the input from {}: {}", source, why),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+/// The name of the environment variable that may contain the input for the given day, such as `AOC_INPUT_07`.
+pub fn variable(day: u8) -> String { format!("AOC_INPUT_{:02}", day) }
+
+/// The path of the input file that is kept alongside each day's solution, such as `src/07/input.txt`.
+pub fn default_path(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src").join(format!("{:02}", day)).join("input.txt")
+}
+
+impl Source {
+    /// Interprets a command-line argument as a source, where `-` refers to the standard input.
+    pub fn from_argument(argument: &str) -> Source {
+        if argument == "-" { Source::Stdin } else { Source::File(PathBuf::from(argument)) }
+    }
+
+    /// Chooses the source for a day. An explicit `argument` takes priority, followed by the day's environment
+    /// variable if it is set, and then by the input file that is kept alongside the day's solution.
+    pub fn for_day(day: u8, argument: Option<&str>) -> Source {
+        match argument {
+            Some(argument) => Source::from_argument(argument),
+            None => {
+                let variable = variable(day);
+                if env::var_os(&variable).is_some() { Source::Env(variable) } else { Source::File(default_path(day)) }
+            }
+        }
+    }
+
+    /// Reads the input, failing if the input is missing or empty.
+    pub fn read(&self) -> Result<String, InputError> {
+        let mut input = String::new();
+        match *self {
+            Source::File(ref path) => {
+                let mut file = File::open(path).map_err(|why| match why.kind() {
+                    io::ErrorKind::NotFound => InputError::Missing(path.clone()),
+                    _ => InputError::Io(self.clone(), why)
+                })?;
+                file.read_to_string(&mut input).map_err(|why| InputError::Io(self.clone(), why))?;
+            },
+            Source::Stdin => {
+                let stdin = io::stdin();
+                stdin.lock().read_to_string(&mut input).map_err(|why| InputError::Io(self.clone(), why))?;
+            },
+            Source::Env(ref variable) => {
+                input = env::var(variable).map_err(|_| InputError::Unset(variable.clone()))?;
+            }
+        }
+
+        if input.trim().is_empty() { Err(InputError::Empty(self.clone())) } else { Ok(input) }
+    }
+}
+
+/// Loads the input for a day's binary, which accepts an optional path (or `-`) as its first argument.
+pub fn from_args(day: u8) -> Result<String, InputError> {
+    Source::for_day(day, env::args().nth(1).as_deref()).read()
+}
+
+/// Loads and parses the input for a day's binary, describing where the input is malformed if it could not be
+/// parsed.
+pub fn parse_args<S: Solution>() -> Result<S::Parsed, String> {
+    let input = from_args(S::DAY).map_err(|why| why.to_string())?;
+    S::parse(&input).map_err(|why| why.diagnose(&input))
+}
+
+#[test]
+fn sources() {
+    assert_eq!(Source::Stdin, Source::from_argument("-"));
+    assert_eq!(Source::File(PathBuf::from("input.txt")), Source::from_argument("input.txt"));
+    assert_eq!(Source::File(PathBuf::from("input.txt")), Source::for_day(7, Some("input.txt")));
+    assert_eq!(Source::File(default_path(99)), Source::for_day(99, None));
+    assert!(default_path(7).ends_with("src/07/input.txt"));
+}
+
+#[test]
+fn missing_and_empty() {
+    match Source::File(default_path(99)).read() {
+        Err(InputError::Missing(path)) => assert_eq!(path, default_path(99)),
+        _ => panic!("expected the input to be missing")
+    }
+
+    env::set_var("AOC_INPUT_TEST_EMPTY", " \n");
+    match Source::Env(String::from("AOC_INPUT_TEST_EMPTY")).read() {
+        Err(InputError::Empty(Source::Env(ref variable))) => assert_eq!(variable, "AOC_INPUT_TEST_EMPTY"),
+        _ => panic!("expected the input to be empty")
+    }
+
+    env::set_var("AOC_INPUT_TEST_VALUE", "R2, L3");
+    assert_eq!("R2, L3", Source::Env(String::from("AOC_INPUT_TEST_VALUE")).read().unwrap());
+}
@@ -0,0 +1,44 @@
+//! Solutions to the 2016 Advent of Code puzzles, shared between the per-day binaries and the `aoc` runner.
+extern crate arrayvec;
+extern crate crypto;
+extern crate num_cpus;
+extern crate time;
+
+pub mod assembunny;
+pub mod bench;
+pub mod input;
+pub mod output;
+pub mod parse;
+pub mod solution;
+pub mod verify;
+pub use solution::Solution;
+
+/// Every binary links against this library, so the allocator that is chosen here is shared by all of them.
+#[cfg(feature = "alloc_system")]
+#[global_allocator]
+static ALLOCATOR: std::alloc::System = std::alloc::System;
+
+/// Day 1: No Time for a Taxicab
+#[path = "01/mod.rs"] pub mod day01;
+/// Day 2: Bathroom Security
+#[path = "02/mod.rs"] pub mod day02;
+/// Day 3: Squares With Three Sides
+#[path = "03/mod.rs"] pub mod day03;
+/// Day 4: Security Through Obscurity
+#[path = "04/mod.rs"] pub mod day04;
+/// Day 5: How About a Nice Game of Chess?
+#[path = "05/mod.rs"] pub mod day05;
+/// Day 6: Signals and Noise
+#[path = "06/mod.rs"] pub mod day06;
+/// Day 7: Internet Protocol Version 7
+#[path = "07/mod.rs"] pub mod day07;
+/// Day 8: Two-Factor Authentication
+#[path = "08/mod.rs"] pub mod day08;
+/// Day 9: Explosives in Cyberspace
+#[path = "09/mod.rs"] pub mod day09;
+/// Day 10: Balance Bots
+#[path = "10/mod.rs"] pub mod day10;
+/// Day 11: Radioisotope Thermoelectric Generators
+#[path = "11/mod.rs"] pub mod day11;
+/// Day 12: Leonardo's Monorail
+#[path = "12/mod.rs"] pub mod day12;
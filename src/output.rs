@@ -0,0 +1,135 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// The formats that answers may be reported in.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Format {
+    /// Human-readable lines, such as `Day 07 part 1: 118`.
+    Text,
+    /// One JSON object per line, such as `{"day":7,"part":1,"answer":"118","elapsed_ns":5120}`.
+    Json,
+    /// Tab-separated values, preceded by a header row.
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+    fn from_str(input: &str) -> Result<Format, String> {
+        match input {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "tsv"  => Ok(Format::Tsv),
+            _      => Err(format!("'{}' is not a format: expected text, json, or tsv", input)),
+        }
+    }
+}
+
+impl Format {
+    /// The header that precedes the records, if the format has one.
+    pub fn header(self) -> Option<&'static str> {
+        match self {
+            Format::Tsv => Some("day\tpart\tanswer\telapsed_ns"),
+            _           => None,
+        }
+    }
+}
+
+/// The answer to one part of a day's puzzle, along with the time that it took to find it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub day:        u8,
+    pub part:       u8,
+    /// The answer as it is displayed, which may span multiple lines, as day 8's screen does.
+    pub answer:     String,
+    /// The time spent parsing the input and solving the part, in nanoseconds.
+    pub elapsed_ns: u64,
+}
+
+impl Record {
+    /// Formats the record as a single entry in the given format.
+    pub fn format(&self, format: Format) -> String {
+        match format {
+            Format::Text => if self.answer.contains('\n') {
+                format!("Day {:02} part {}:\n{}", self.day, self.part, self.answer.trim_end())
+            } else {
+                format!("Day {:02} part {}: {}", self.day, self.part, self.answer)
+            },
+            Format::Json => format!(r#"{{"day":{},"part":{},"answer":"{}","elapsed_ns":{}}}"#,
+                self.day, self.part, escape_json(&self.answer), self.elapsed_ns),
+            Format::Tsv => format!("{}\t{}\t{}\t{}", self.day, self.part, escape_tsv(&self.answer), self.elapsed_ns),
+        }
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(&self.format(Format::Text)) }
+}
+
+/// Escapes a string so that it may be placed between the quotes of a JSON string.
+pub fn escape_json(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for character in input.chars() {
+        match character {
+            '"'  => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if (c as u32) < 0x20 => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+/// Escapes a string so that it fits within a single TSV field.
+pub fn escape_tsv(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for character in input.chars() {
+        match character {
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+/// Reverses `escape_tsv`, returning `None` if the field contains an unknown escape sequence.
+pub fn unescape_tsv(input: &str) -> Option<String> {
+    let mut output = String::with_capacity(input.len());
+    let mut characters = input.chars();
+    while let Some(character) = characters.next() {
+        if character != '\\' { output.push(character); continue }
+        match characters.next() {
+            Some('\\') => output.push('\\'),
+            Some('n')  => output.push('\n'),
+            Some('r')  => output.push('\r'),
+            Some('t')  => output.push('\t'),
+            _          => return None,
+        }
+    }
+    Some(output)
+}
+
+#[test]
+fn record_formats() {
+    let record = Record { day: 8, part: 2, answer: String::from("# \"#\"\n#\t#\n"), elapsed_ns: 42 };
+    assert_eq!(r##"{"day":8,"part":2,"answer":"# \"#\"\n#\t#\n","elapsed_ns":42}"##, record.format(Format::Json));
+    assert_eq!("8\t2\t# \"#\"\\n#\\t#\\n\t42", record.format(Format::Tsv));
+    assert_eq!("Day 08 part 2:\n# \"#\"\n#\t#", record.format(Format::Text));
+
+    let record = Record { day: 7, part: 1, answer: String::from("118"), elapsed_ns: 5120 };
+    assert_eq!(r#"{"day":7,"part":1,"answer":"118","elapsed_ns":5120}"#, record.format(Format::Json));
+    assert_eq!("Day 07 part 1: 118", record.format(Format::Text));
+}
+
+#[test]
+fn tsv_round_trip() {
+    let answer = "#  #\n\\ \t\r";
+    assert_eq!(Some(String::from(answer)), unescape_tsv(&escape_tsv(answer)));
+    assert_eq!(None, unescape_tsv("\\x"));
+    assert_eq!(None, unescape_tsv("trailing\\"));
+}
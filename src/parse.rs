@@ -0,0 +1,81 @@
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+
+/// An error that was found while parsing puzzle input, where `K` is the day's own kind of error.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError<K> {
+    /// The line that the error was found on, counting from one.
+    pub line:   usize,
+    /// The column that the offending text begins at, counting from one.
+    pub column: usize,
+    /// The text that could not be parsed.
+    pub text:   String,
+    /// Why the text could not be parsed.
+    pub kind:   K,
+}
+
+impl<K> ParseError<K> {
+    /// Creates an error for the offending `text`, which is a slice of the `line` found at line `number`.
+    pub fn new(kind: K, number: usize, line: &str, text: &str) -> ParseError<K> {
+        ParseError { line: number, column: column(line, text), text: String::from(text), kind: kind }
+    }
+}
+
+impl<K: Display> Display for ParseError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.text.is_empty() {
+            write!(f, "line {}, column {}: {}", self.line, self.column, self.kind)
+        } else {
+            write!(f, "line {}, column {}: {}: '{}'", self.line, self.column, self.kind, self.text)
+        }
+    }
+}
+
+impl<K: Display> ParseError<K> {
+    /// Describes the error along with the line of the `input` that it was found on, underlining the
+    /// offending text.
+    pub fn diagnose(&self, input: &str) -> String {
+        let line   = input.lines().nth(self.line.saturating_sub(1)).unwrap_or("");
+        let gutter = self.line.to_string().len();
+        format!("{}\n {} | {}\n {} | {}{}", self, self.line, line, " ".repeat(gutter),
+            " ".repeat(self.column.saturating_sub(1)), "^".repeat(self.text.chars().count().max(1)))
+    }
+}
+
+impl<K: Debug + Display> Error for ParseError<K> {}
+
+/// Finds the column, counting characters from one, that `text` begins at when it is a slice of `line`.
+/// Text that is not a slice of the line is considered to begin after the end of the line.
+pub fn column(line: &str, text: &str) -> usize {
+    let (start, end) = (line.as_ptr() as usize, line.as_ptr() as usize + line.len());
+    let offset = text.as_ptr() as usize;
+    let offset = if offset >= start && offset <= end { offset - start } else { line.len() };
+    line[..offset].chars().count() + 1
+}
+
+/// Iterates the lines of the input alongside their line numbers, counting from one.
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input.lines().enumerate().map(|(id, line)| (id + 1, line))
+}
+
+#[test]
+fn columns() {
+    let line = "rotate row y=0 by 4";
+    let by = line.split_whitespace().nth(3).unwrap();
+    assert_eq!(16, column(line, by));
+    assert_eq!(1, column(line, line));
+    assert_eq!(20, column(line, &line[line.len()..]));
+    assert_eq!(20, column(line, "elsewhere"));
+    assert_eq!(3, column("é x", &"é x"[3..]));
+
+    let error = ParseError::new("expected a number", 2, line, by);
+    assert_eq!("line 2, column 16: expected a number: 'by'", error.to_string());
+    assert_eq!("line 2, column 20: expected a number", ParseError::new("expected a number", 2, line, "").to_string());
+}
+
+#[test]
+fn diagnostics() {
+    let input = "5 10 25\n3 4x 5\n";
+    let error = ParseError::new("expected a number", 2, input.lines().nth(1).unwrap(), &input[10..12]);
+    assert_eq!("line 2, column 3: expected a number: '4x'\n 2 | 3 4x 5\n   |   ^^", error.diagnose(input));
+}
@@ -0,0 +1,27 @@
+use parse::ParseError;
+use std::fmt::Display;
+
+/// A puzzle solver, split into a parsing phase that is shared by both parts of the puzzle,
+/// and the two parts that are solved from the parsed input.
+pub trait Solution {
+    /// The day of the advent calendar that this puzzle belongs to.
+    const DAY: u8;
+
+    /// The form that the puzzle input takes once it has been parsed.
+    type Parsed;
+    /// The answer to the first part of the puzzle.
+    type PartOne: Display;
+    /// The answer to the second part of the puzzle.
+    type PartTwo: Display;
+    /// Why the puzzle input could not be parsed.
+    type Error: Display;
+
+    /// Parses the raw puzzle input, describing where and why it is malformed if it cannot be parsed.
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError<Self::Error>>;
+
+    /// Solves the first part of the puzzle.
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne;
+
+    /// Solves the second part of the puzzle.
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo;
+}
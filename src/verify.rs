@@ -0,0 +1,160 @@
+use crypto::digest::Digest;
+use crypto::md5::Md5;
+use output::{escape_tsv, unescape_tsv};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// The header row that begins every answers manifest.
+const HEADER: &'static str = "day\tpart\tinput_md5\tanswer";
+
+/// Identifies a recorded answer by the day and part that it solves, and the hash of the input it was found with.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Key {
+    pub day:   u8,
+    pub part:  u8,
+    /// The MD5 digest of the puzzle input, as a hexadecimal string.
+    pub input: String,
+}
+
+impl Key {
+    /// Creates a key for the given day and part by hashing the puzzle `input`.
+    pub fn new(day: u8, part: u8, input: &str) -> Key { Key { day: day, part: part, input: hash(input) } }
+}
+
+/// The result of comparing an answer against the manifest.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    /// The answer matches the answer that was recorded for this input.
+    Pass,
+    /// The answer differs from the answer that was recorded for this input.
+    Regression { expected: String },
+    /// No answer has been recorded for this input.
+    Unrecorded,
+}
+
+/// Errors that may occur while loading an answers manifest.
+#[derive(Debug)]
+pub enum ManifestError {
+    /// The manifest could not be read.
+    Io(PathBuf, io::Error),
+    /// The line, counting from one, is not a valid manifest entry.
+    Malformed(usize, String),
+}
+
+impl Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ManifestError::Io(ref path, ref why) => write!(f, "unable to read '{}': {}", path.display(), why),
+            ManifestError::Malformed(line, ref text) => write!(f, "line {} is not a valid entry: '{}'", line, text),
+        }
+    }
+}
+
+impl Error for ManifestError {}
+
+/// Known-good answers, keyed by the day, part, and the hash of the input that each answer was found with.
+#[derive(Debug, Default, PartialEq)]
+pub struct Manifest { answers: BTreeMap<Key, String> }
+
+impl Manifest {
+    /// Parses a manifest of tab-separated `day`, `part`, `input_md5`, and `answer` columns, after a header row.
+    /// Answers are escaped in the same manner as the runner's TSV output.
+    pub fn parse(input: &str) -> Result<Manifest, ManifestError> {
+        let mut manifest = Manifest::default();
+        for (id, line) in input.lines().enumerate() {
+            if line.is_empty() || (id == 0 && line == HEADER) { continue }
+            let malformed = || ManifestError::Malformed(id + 1, String::from(line));
+            let mut fields = line.splitn(4, '\t');
+            let day    = fields.next().and_then(|x| x.parse::<u8>().ok()).ok_or_else(&malformed)?;
+            let part   = fields.next().and_then(|x| x.parse::<u8>().ok()).ok_or_else(&malformed)?;
+            let hash   = fields.next().ok_or_else(&malformed)?;
+            let answer = fields.next().and_then(unescape_tsv).ok_or_else(&malformed)?;
+            manifest.answers.insert(Key { day: day, part: part, input: String::from(hash) }, answer);
+        }
+        Ok(manifest)
+    }
+
+    /// Loads the manifest at `path`, which is considered to be empty if it does not exist yet.
+    pub fn load(path: &Path) -> Result<Manifest, ManifestError> {
+        let mut input = String::new();
+        match File::open(path) {
+            Ok(mut file) => {
+                file.read_to_string(&mut input).map_err(|why| ManifestError::Io(path.to_path_buf(), why))?;
+                Manifest::parse(&input)
+            },
+            Err(ref why) if why.kind() == io::ErrorKind::NotFound => Ok(Manifest::default()),
+            Err(why) => Err(ManifestError::Io(path.to_path_buf(), why)),
+        }
+    }
+
+    /// Writes the manifest to `path`, replacing the previous manifest.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        File::create(path).and_then(|mut file| file.write_all(self.to_string().as_bytes()))
+    }
+
+    /// Compares an `answer` against the answer that was recorded for the `key`.
+    pub fn check(&self, key: &Key, answer: &str) -> Verdict {
+        match self.answers.get(key) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Regression { expected: expected.clone() },
+            None => Verdict::Unrecorded,
+        }
+    }
+
+    /// Records the `answer` as the known-good answer for the `key`, replacing any previous answer.
+    pub fn record(&mut self, key: Key, answer: String) { self.answers.insert(key, answer); }
+
+    /// The number of answers in the manifest.
+    pub fn len(&self) -> usize { self.answers.len() }
+
+    /// Determines if no answers have been recorded.
+    pub fn is_empty(&self) -> bool { self.answers.is_empty() }
+}
+
+impl Display for Manifest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+        for (key, answer) in &self.answers {
+            writeln!(f, "{}\t{}\t{}\t{}", key.day, key.part, key.input, escape_tsv(answer))?;
+        }
+        Ok(())
+    }
+}
+
+/// Hashes puzzle input with MD5, so that answers can be matched to the input that they were found with.
+pub fn hash(input: &str) -> String {
+    let mut md5 = Md5::new();
+    md5.input_str(input);
+    md5.result_str()
+}
+
+#[test]
+fn manifest() {
+    let screen = "#  #\n ## \n";
+    let mut manifest = Manifest::default();
+    manifest.record(Key::new(7, 1, "abba[mnop]qrst"), String::from("1"));
+    manifest.record(Key::new(8, 2, "rect 2x1"), String::from(screen));
+
+    let manifest = Manifest::parse(&manifest.to_string()).unwrap();
+    assert_eq!(2, manifest.len());
+    assert_eq!(Verdict::Pass, manifest.check(&Key::new(7, 1, "abba[mnop]qrst"), "1"));
+    assert_eq!(Verdict::Pass, manifest.check(&Key::new(8, 2, "rect 2x1"), screen));
+    assert_eq!(Verdict::Regression { expected: String::from("1") },
+        manifest.check(&Key::new(7, 1, "abba[mnop]qrst"), "0"));
+    assert_eq!(Verdict::Unrecorded, manifest.check(&Key::new(7, 1, "abcd[bddb]xyyx"), "0"));
+    assert_eq!(Verdict::Unrecorded, manifest.check(&Key::new(7, 2, "abba[mnop]qrst"), "1"));
+}
+
+#[test]
+fn malformed_manifest() {
+    match Manifest::parse("day\tpart\tinput_md5\tanswer\n7\tone\tabc\t1\n") {
+        Err(ManifestError::Malformed(line, _)) => assert_eq!(2, line),
+        _ => panic!("expected the second line to be malformed")
+    }
+    assert!(Manifest::parse("7\t1\tabc").is_err());
+    assert_eq!("d41d8cd98f00b204e9800998ecf8427e", hash(""));
+}